@@ -16,14 +16,7 @@ fi
 
 
 if [[ "$event" == "after_asset_hash" || "$env" == "dev" ]]; then
-    mkdir -p dist/{counter,temperature,timer,crud,flight}
-
     # Generate html
-    ./target/$target/sevenguis_cli home_page > dist/index.html
-    ./target/$target/sevenguis_cli counter_page > dist/counter/index.html
-    ./target/$target/sevenguis_cli temperature_page > dist/temperature/index.html
-    ./target/$target/sevenguis_cli timer_page > dist/timer/index.html
-    ./target/$target/sevenguis_cli crud_page > dist/crud/index.html
-    ./target/$target/sevenguis_cli flight_page > dist/flight/index.html
+    ./target/$target/sevenguis_cli build --out dist
 fi
 
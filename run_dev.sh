@@ -1,7 +1,5 @@
 #!/bin/bash
 set -e
 
-(
-    cd dist
-    python3 -m http.server 8003
-)
+# Pages are rendered per request, static assets come from dist
+cargo run -p sevenguis_server -- --static dist
@@ -1,48 +1,67 @@
-use polyester::page::Page;
-use polyester::time;
-use sevenguis_core::counter_page;
+use sevenguis_core::clock;
+use sevenguis_core::clock::Clock;
+use sevenguis_core::clock::ManualClock;
+use sevenguis_core::clock::SystemClock;
+use sevenguis_core::crud_codec;
+use sevenguis_core::crud_codec::Format;
+use sevenguis_core::crud_codec::ImportMode;
 use sevenguis_core::crud_page;
-use sevenguis_core::flight_page;
-use sevenguis_core::home_page;
-use sevenguis_core::temperature_page;
-use sevenguis_core::timer_page;
+use sevenguis_core::registry;
+use sevenguis_core::registry::PageInfo;
 use std::env;
-use std::time::SystemTime;
+use std::fs;
+use std::panic;
+use std::path::Path;
+use std::process;
+use std::rc::Rc;
 
 fn main() {
     let args_: Vec<String> = env::args().collect();
     let args: Vec<&str> = args_.iter().map(|s| s.as_ref()).collect();
-    let now = posix_now();
+    let (args, now) = take_option(&args[1..], "--now");
 
-    match args[1..] {
-        ["home_page"] => {
-            let page = home_page::HomePage {};
-            render_html(page);
+    // --now renders time dependent pages at a chosen instant instead of the current time
+    let clock: Rc<dyn Clock> = match now.map(clock::parse_iso8601).transpose() {
+        Ok(Some(now)) => Rc::new(ManualClock::new(now)),
+        Ok(None) => Rc::new(SystemClock),
+
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+
+    match args[..] {
+        ["build"] => {
+            exit_on_error(build_site("dist", &clock));
+        }
+
+        ["build", "--out", out_dir] => {
+            exit_on_error(build_site(out_dir, &clock));
+        }
+
+        ["crud", "export", format] => {
+            exit_on_error(crud_export(format, None));
         }
 
-        ["counter_page"] => {
-            let page = counter_page::CounterPage {};
-            render_html(page);
+        ["crud", "export", format, dataset] => {
+            exit_on_error(crud_export(format, Some(dataset)));
         }
 
-        ["temperature_page"] => {
-            let page = temperature_page::TemperaturePage {};
-            render_html(page);
+        ["crud", "import", file, dataset] => {
+            exit_on_error(crud_import(file, dataset, "merge"));
         }
 
-        ["timer_page"] => {
-            let page = timer_page::TimerPage { initial_time: now };
-            render_html(page);
+        ["crud", "import", file, dataset, mode] => {
+            exit_on_error(crud_import(file, dataset, mode));
         }
 
-        ["crud_page"] => {
-            let page = crud_page::CrudPage {};
-            render_html(page);
+        [name] => {
+            exit_on_error(print_page(name, None, &clock));
         }
 
-        ["flight_page"] => {
-            let page = flight_page::FlightPage { initial_time: now };
-            render_html(page);
+        [name, "--model", model_path] => {
+            exit_on_error(print_page(name, Some(model_path), &clock));
         }
 
         _ => {
@@ -51,17 +70,151 @@ fn main() {
     }
 }
 
-fn posix_now() -> time::Posix {
-    let millis = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap()
-        .as_millis();
+// Removes an option and its value from the arguments, wherever it appears
+fn take_option<'a>(args: &[&'a str], name: &str) -> (Vec<&'a str>, Option<&'a str>) {
+    match args.iter().position(|arg| *arg == name) {
+        Some(index) if index + 1 < args.len() => {
+            let mut rest = args.to_vec();
+            let value = rest.remove(index + 1);
+            rest.remove(index);
+
+            (rest, Some(value))
+        }
+
+        _ => (args.to_vec(), None),
+    }
+}
+
+fn print_page(name: &str, model_path: Option<&str>, clock: &Rc<dyn Clock>) -> Result<(), String> {
+    // Pages used to be addressed by module name, e.g. counter_page
+    let slug = name.strip_suffix("_page").unwrap_or(name);
+    let page = registry::find(slug).ok_or_else(|| format!("Invalid command: {}", name))?;
 
-    time::Posix::from_millis(millis as i128)
+    let model_json = model_path
+        .map(|path| {
+            fs::read_to_string(path).map_err(|err| format!("Failed to read {}: {}", path, err))
+        })
+        .transpose()?;
+
+    let html = (page.new)(clock.clone()).render(model_json.as_deref())?;
+    println!("{}", html);
+
+    Ok(())
 }
 
-fn render_html<Model, Msg, AppEffect>(page: impl Page<Model, Msg, AppEffect>) {
-    let (model, _effects) = page.init();
-    let page = page.view(&model);
-    println!("{}", page.to_markup().into_string());
+// Renders every page into its route directory, continuing past failures so they are all reported
+fn build_site(out_dir: &str, clock: &Rc<dyn Clock>) -> Result<(), String> {
+    let pages = registry::PAGES;
+    let mut failed = 0;
+
+    for page in pages {
+        let path = Path::new(out_dir)
+            .join(page.route.trim_start_matches('/'))
+            .join("index.html");
+
+        match build_page(page, &path, clock) {
+            Ok(()) => {
+                println!("Built {} -> {}", page.slug, path.display());
+            }
+
+            Err(err) => {
+                eprintln!("Failed to build {}: {}", page.slug, err);
+                failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "Built {} of {} pages into {}",
+        pages.len() - failed,
+        pages.len(),
+        out_dir
+    );
+
+    if failed > 0 {
+        Err(format!("{} pages failed to build", failed))
+    } else {
+        Ok(())
+    }
+}
+
+fn build_page(page: &PageInfo, path: &Path, clock: &Rc<dyn Clock>) -> Result<(), String> {
+    let html = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        (page.new)(clock.clone()).render(None)
+    }))
+    .map_err(|_| "Rendering panicked".to_string())??;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("Failed to create {}: {}", dir.display(), err))?;
+    }
+
+    fs::write(path, html).map_err(|err| format!("Failed to write {}: {}", path.display(), err))
+}
+
+fn exit_on_error(result: Result<(), String>) {
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn read_dataset(path: &str) -> Result<crud_page::StoredPeople, String> {
+    let content =
+        fs::read_to_string(path).map_err(|err| format!("Failed to read {}: {}", path, err))?;
+
+    crud_page::StoredPeople::decode(&content)
+}
+
+// Prints the people in a dataset, or the seed data if no dataset is given
+fn crud_export(format: &str, dataset: Option<&str>) -> Result<(), String> {
+    let format: Format = format.parse()?;
+
+    let people = match dataset {
+        Some(path) => read_dataset(path)?.people,
+        None => crud_page::seed_people(),
+    };
+
+    print!("{}", crud_codec::encode(&people, format));
+
+    Ok(())
+}
+
+// Imports a csv or json file into a dataset, creating the dataset if it doesn't exist
+fn crud_import(file: &str, dataset: &str, mode: &str) -> Result<(), String> {
+    let mode: ImportMode = mode.parse()?;
+    let format = Format::from_filename(file)
+        .ok_or_else(|| format!("Can't tell the format of {}, expected .csv or .json", file))?;
+
+    let content =
+        fs::read_to_string(file).map_err(|err| format!("Failed to read {}: {}", file, err))?;
+    let decoded = crud_codec::decode(&content, format)?;
+
+    let existing = if Path::new(dataset).exists() {
+        read_dataset(dataset)?
+    } else {
+        crud_page::StoredPeople::new(1, vec![])
+    };
+
+    let imported = crud_codec::apply(&existing.people, existing.next_id, decoded, mode);
+
+    for err in &imported.errors {
+        eprintln!("{}: {}", file, err);
+    }
+
+    let total = imported.people.len();
+    let skipped = imported.errors.len();
+    let stored = crud_page::StoredPeople::new(imported.next_id, imported.people);
+
+    let json = serde_json::to_string_pretty(&stored)
+        .map_err(|err| format!("Failed to encode dataset: {}", err))?;
+
+    fs::write(dataset, json).map_err(|err| format!("Failed to write {}: {}", dataset, err))?;
+
+    println!(
+        "Imported {} people into {} ({} total, {} rows skipped)",
+        imported.imported, dataset, total, skipped
+    );
+
+    Ok(())
 }
@@ -0,0 +1,97 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Output;
+
+// A fresh temp dir for each test, so they can run in parallel
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("sevenguis_cli_{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn cli(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sevenguis_cli"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn exports_the_seed_data() {
+    let output = cli(&["crud", "export", "csv"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "id,first_name,last_name\n1,Hans,Emil\n2,Max,Mustermann\n3,Roman,Tisch\n"
+    );
+}
+
+#[test]
+fn exported_people_import_into_a_dataset() {
+    let dir = temp_dir("round_trip");
+    let file = dir.join("people.json");
+    let dataset = dir.join("dataset.json");
+    let dataset = dataset.to_str().unwrap();
+
+    fs::write(&file, stdout(&cli(&["crud", "export", "json"]))).unwrap();
+
+    let output = cli(&["crud", "import", file.to_str().unwrap(), dataset, "replace"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Imported 3 people"));
+
+    let output = cli(&["crud", "export", "csv", dataset]);
+    assert_eq!(
+        stdout(&output),
+        "id,first_name,last_name\n1,Hans,Emil\n2,Max,Mustermann\n3,Roman,Tisch\n"
+    );
+}
+
+#[test]
+fn merge_continues_from_the_dataset_next_id() {
+    let dir = temp_dir("merge");
+    let file = dir.join("people.csv");
+    let dataset = dir.join("dataset.json");
+
+    fs::write(
+        &dataset,
+        r#"{"version":1,"nextId":9,"people":[{"id":1,"first_name":"Ada","last_name":"Lovelace"}]}"#,
+    )
+    .unwrap();
+    fs::write(
+        &file,
+        "first_name,last_name\nAda,Lovelace\n\"Jr, Sammy\",Davis\n,Nobody\n",
+    )
+    .unwrap();
+
+    let dataset = dataset.to_str().unwrap();
+    let output = cli(&["crud", "import", file.to_str().unwrap(), dataset]);
+
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Imported 1 people"));
+    assert!(String::from_utf8(output.stderr).unwrap().contains("Row 4"));
+
+    let output = cli(&["crud", "export", "csv", dataset]);
+    assert_eq!(
+        stdout(&output),
+        "id,first_name,last_name\n1,Ada,Lovelace\n9,\"Jr, Sammy\",Davis\n"
+    );
+}
+
+#[test]
+fn unknown_formats_fail() {
+    let output = cli(&["crud", "export", "xml"]);
+
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Unknown format: xml\n"
+    );
+}
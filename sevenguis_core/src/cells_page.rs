@@ -0,0 +1,163 @@
+use crate::components::Input;
+use crate::hydration::Validate;
+use crate::layout;
+use crate::registry;
+use crate::spreadsheet;
+use crate::spreadsheet::CellId;
+use crate::spreadsheet::CellValue;
+use crate::spreadsheet::Sheet;
+use maud::html;
+use polyester::browser;
+use polyester::browser::DomId;
+use polyester::browser::Effects;
+use polyester::browser::ToDomId;
+use polyester::page::Page;
+use polyester::page::PageMarkup;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    pub sheet: Sheet,
+    pub editing: Option<CellId>,
+}
+
+impl Validate for Model {}
+
+pub struct CellsPage {}
+
+impl Page<Model, Msg, AppEffect> for CellsPage {
+    fn id(&self) -> DomId {
+        DomId::new("sevenguis")
+    }
+
+    fn init(&self) -> (Model, Effects<Msg, AppEffect>) {
+        let model = Model {
+            sheet: Sheet::new(),
+            editing: None,
+        };
+
+        let effects = vec![];
+
+        (model, effects)
+    }
+
+    fn subscriptions(&self, model: &Model) -> browser::Subscriptions<Msg, AppEffect> {
+        if model.editing.is_some() {
+            vec![browser::on_change_string(&Id::Editor, Msg::EditCommitted)]
+        } else {
+            vec![]
+        }
+    }
+
+    fn update(&self, msg: &Msg, model: &mut Model) -> Result<Effects<Msg, AppEffect>, String> {
+        match msg {
+            Msg::EditStarted(cell_id) => {
+                model.editing = Some(*cell_id);
+
+                Ok(vec![])
+            }
+
+            Msg::EditCommitted(content) => {
+                if let Some(cell_id) = model.editing.take() {
+                    model.sheet.set(cell_id, content);
+                }
+
+                Ok(vec![])
+            }
+
+            Msg::EditCancelled => {
+                model.editing = None;
+
+                Ok(vec![])
+            }
+        }
+    }
+
+    fn view(&self, model: &Model) -> PageMarkup {
+        PageMarkup {
+            head: view_head(),
+            body: view_body(&self.id(), model),
+        }
+    }
+}
+
+#[derive(strum_macros::Display, polyester_macro::ToDomId)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Id {
+    Editor,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Msg {
+    EditStarted(CellId),
+    EditCommitted(String),
+    EditCancelled,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AppEffect {}
+
+fn view_head() -> maud::Markup {
+    layout::view_head(&registry::CELLS)
+}
+
+fn view_body(page_id: &browser::DomId, model: &Model) -> maud::Markup {
+    let content = html! {
+        div class="p-4" {
+            div class="overflow-auto border border-gray-300" style="max-height: 80vh;" {
+                table class="border-collapse text-sm" {
+                    thead {
+                        tr {
+                            th class="sticky top-0 bg-gray-100 border border-gray-300 w-10" {}
+                            @for column in 0..spreadsheet::COLUMNS {
+                                th class="sticky top-0 bg-gray-100 border border-gray-300 w-24" {
+                                    (CellId::column_name(column))
+                                }
+                            }
+                        }
+                    }
+
+                    tbody {
+                        @for row in 0..spreadsheet::ROWS {
+                            tr {
+                                th class="bg-gray-100 border border-gray-300" {
+                                    (row)
+                                }
+                                @for column in 0..spreadsheet::COLUMNS {
+                                    (view_cell(model, CellId { column, row }))
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    layout::view_body(page_id, &registry::CELLS, None, content)
+}
+
+fn view_cell(model: &Model, cell_id: CellId) -> maud::Markup {
+    let is_editing = model.editing == Some(cell_id);
+    let value = model.sheet.value(&cell_id);
+    let alignment = match value {
+        CellValue::Number(_) => "text-right",
+        CellValue::Error(_) => "text-center text-red-600",
+        CellValue::Empty | CellValue::Text(_) => "text-left",
+    };
+
+    html! {
+        @if is_editing {
+            td class="border border-gray-300 p-0" {
+                (Input::text(Id::Editor, model.sheet.content(&cell_id)).class("w-24 px-1 py-0").autofocus())
+            }
+        } @else {
+            td data-cell=(cell_id) title=(model.sheet.content(&cell_id)) class=(format!("border border-gray-300 px-1 h-6 w-24 max-w-[6rem] truncate cursor-cell {}", alignment)) {
+                (value)
+            }
+        }
+    }
+}
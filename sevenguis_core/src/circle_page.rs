@@ -0,0 +1,430 @@
+use crate::components::Button;
+use crate::components::Field;
+use crate::components::Input;
+use crate::hydration;
+use crate::hydration::Validate;
+use crate::layout;
+use crate::page_error;
+use crate::page_error::ErrorState;
+use crate::page_error::PageError;
+use crate::registry;
+use crate::runtime;
+use maud::html;
+use polyester::browser;
+use polyester::browser::DomId;
+use polyester::browser::Effects;
+use polyester::browser::ToDomId;
+use polyester::page::Page;
+use polyester::page::PageMarkup;
+use serde::{Deserialize, Serialize};
+
+const CANVAS_WIDTH: u32 = 500;
+const CANVAS_HEIGHT: u32 = 300;
+const DEFAULT_DIAMETER: u32 = 30;
+const MIN_DIAMETER: u32 = 4;
+const MAX_DIAMETER: u32 = 200;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    pub next_id: u32,
+    pub circles: Vec<Circle>,
+    pub selected_id: Option<u32>,
+    pub dialog: Option<Dialog>,
+    pub undo_stack: Vec<Change>,
+    pub redo_stack: Vec<Change>,
+    pub error: Option<PageError>,
+}
+
+impl Validate for Model {
+    fn validate(&self) -> Result<(), String> {
+        hydration::validate_ids(self.circles.iter().map(|c| c.id), self.next_id)?;
+
+        match self.selected_id {
+            Some(id) if !self.circles.iter().any(|c| c.id == id) => {
+                Err(format!("Selected circle {} doesn't exist", id))
+            }
+
+            _ => Ok(()),
+        }
+    }
+}
+
+impl ErrorState for Model {
+    fn error(&self) -> Option<&PageError> {
+        self.error.as_ref()
+    }
+
+    fn set_error(&mut self, error: Option<PageError>) {
+        self.error = error;
+    }
+}
+
+pub struct CirclePage {}
+
+impl Page<Model, Msg, AppEffect> for CirclePage {
+    fn id(&self) -> DomId {
+        DomId::new("sevenguis")
+    }
+
+    fn init(&self) -> (Model, Effects<Msg, AppEffect>) {
+        let model = Model {
+            next_id: 1,
+            circles: vec![],
+            selected_id: None,
+            dialog: None,
+            undo_stack: vec![],
+            redo_stack: vec![],
+            error: None,
+        };
+
+        let effects = vec![];
+
+        (model, effects)
+    }
+
+    fn subscriptions(&self, model: &Model) -> browser::Subscriptions<Msg, AppEffect> {
+        if model.dialog.is_some() {
+            vec![
+                browser::on_input(&Id::Diameter, Msg::DiameterChanged),
+                browser::on_click(&Id::CloseDialog, Msg::DialogClosed),
+                browser::on_click(&page_error::Id::DismissError, Msg::ErrorDismissed),
+            ]
+        } else {
+            vec![
+                browser::on_click(&Id::Undo, Msg::Undo),
+                browser::on_click(&Id::Redo, Msg::Redo),
+                browser::on_click(&page_error::Id::DismissError, Msg::ErrorDismissed),
+            ]
+        }
+    }
+
+    fn update(&self, msg: &Msg, model: &mut Model) -> Result<Effects<Msg, AppEffect>, String> {
+        runtime::update(model, |model| self.try_update(msg, model))
+    }
+
+    fn view(&self, model: &Model) -> PageMarkup {
+        PageMarkup {
+            head: view_head(),
+            body: view_body(&self.id(), model),
+        }
+    }
+}
+
+impl CirclePage {
+    fn try_update(
+        &self,
+        msg: &Msg,
+        model: &mut Model,
+    ) -> Result<Effects<Msg, AppEffect>, PageError> {
+        match msg {
+            Msg::CanvasClicked(point) => {
+                if model.dialog.is_some() {
+                    return Ok(vec![]);
+                }
+
+                match nearest_circle(&model.circles, point) {
+                    Some(circle) => {
+                        model.selected_id = Some(circle.id);
+                    }
+
+                    None => {
+                        let circle = Circle {
+                            id: model.next_id,
+                            center: point.clone(),
+                            diameter: DEFAULT_DIAMETER,
+                        };
+
+                        model.next_id += 1;
+                        model.selected_id = Some(circle.id);
+                        model.circles.push(circle.clone());
+                        model.undo_stack.push(Change::Created(circle));
+                        model.redo_stack.clear();
+                    }
+                }
+
+                Ok(vec![])
+            }
+
+            Msg::CanvasRightClicked(point) => {
+                if model.dialog.is_some() {
+                    return Ok(vec![]);
+                }
+
+                if let Some(circle) = nearest_circle(&model.circles, point) {
+                    model.selected_id = Some(circle.id);
+                    model.dialog = Some(Dialog {
+                        circle_id: circle.id,
+                        original_diameter: circle.diameter,
+                    });
+                }
+
+                Ok(vec![])
+            }
+
+            Msg::PointerMoved(point) => {
+                if model.dialog.is_none() {
+                    model.selected_id = nearest_circle(&model.circles, point).map(|c| c.id);
+                }
+
+                Ok(vec![])
+            }
+
+            Msg::PointerLeft => {
+                if model.dialog.is_none() {
+                    model.selected_id = None;
+                }
+
+                Ok(vec![])
+            }
+
+            Msg::DiameterChanged(value) => {
+                let circle_id = model
+                    .dialog
+                    .as_ref()
+                    .map(|dialog| dialog.circle_id)
+                    .ok_or_else(|| {
+                        PageError::invalid_state("adjust diameter", "No circle is being adjusted")
+                    })?;
+
+                let diameter: u32 = value
+                    .parse()
+                    .map_err(|err| PageError::parse("diameter", err))?;
+
+                let circle = model.find_circle_mut(circle_id).ok_or_else(|| {
+                    PageError::invalid_state(
+                        "adjust diameter",
+                        "Failed to find circle being adjusted",
+                    )
+                })?;
+
+                circle.diameter = diameter.clamp(MIN_DIAMETER, MAX_DIAMETER);
+
+                Ok(vec![])
+            }
+
+            Msg::DialogClosed => {
+                if let Some(dialog) = model.dialog.clone() {
+                    let new_diameter = model
+                        .find_circle_mut(dialog.circle_id)
+                        .map(|circle| circle.diameter)
+                        .ok_or_else(|| {
+                            PageError::invalid_state(
+                                "close dialog",
+                                "Failed to find circle being adjusted",
+                            )
+                        })?;
+
+                    model.dialog = None;
+
+                    if new_diameter != dialog.original_diameter {
+                        model.undo_stack.push(Change::Resized {
+                            id: dialog.circle_id,
+                            from: dialog.original_diameter,
+                            to: new_diameter,
+                        });
+                        model.redo_stack.clear();
+                    }
+                }
+
+                Ok(vec![])
+            }
+
+            Msg::Undo => {
+                if let Some(change) = model.undo_stack.pop() {
+                    model.revert(&change);
+                    model.redo_stack.push(change);
+                }
+
+                Ok(vec![])
+            }
+
+            Msg::Redo => {
+                if let Some(change) = model.redo_stack.pop() {
+                    model.apply(&change);
+                    model.undo_stack.push(change);
+                }
+
+                Ok(vec![])
+            }
+
+            Msg::ErrorDismissed => {
+                model.error = None;
+                Ok(vec![])
+            }
+        }
+    }
+}
+
+impl Model {
+    fn find_circle_mut(&mut self, id: u32) -> Option<&mut Circle> {
+        self.circles.iter_mut().find(|circle| circle.id == id)
+    }
+
+    fn apply(&mut self, change: &Change) {
+        match change {
+            Change::Created(circle) => {
+                self.circles.push(circle.clone());
+            }
+
+            Change::Resized { id, to, .. } => {
+                if let Some(circle) = self.find_circle_mut(*id) {
+                    circle.diameter = *to;
+                }
+            }
+        }
+    }
+
+    fn revert(&mut self, change: &Change) {
+        match change {
+            Change::Created(circle) => {
+                self.circles.retain(|c| c.id != circle.id);
+
+                if self.selected_id == Some(circle.id) {
+                    self.selected_id = None;
+                }
+            }
+
+            Change::Resized { id, from, .. } => {
+                if let Some(circle) = self.find_circle_mut(*id) {
+                    circle.diameter = *from;
+                }
+            }
+        }
+    }
+}
+
+#[derive(strum_macros::Display, polyester_macro::ToDomId)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Id {
+    Canvas,
+    Undo,
+    Redo,
+    Diameter,
+    CloseDialog,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Msg {
+    CanvasClicked(Point),
+    CanvasRightClicked(Point),
+    PointerMoved(Point),
+    PointerLeft,
+    DiameterChanged(String),
+    DialogClosed,
+    Undo,
+    Redo,
+    ErrorDismissed,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AppEffect {}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    fn distance_to(&self, other: &Point) -> f64 {
+        let dx = f64::from(self.x - other.x);
+        let dy = f64::from(self.y - other.y);
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Circle {
+    pub id: u32,
+    pub center: Point,
+    pub diameter: u32,
+}
+
+impl Circle {
+    fn contains(&self, point: &Point) -> bool {
+        self.center.distance_to(point) <= f64::from(self.diameter) / 2.0
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dialog {
+    pub circle_id: u32,
+    pub original_diameter: u32,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Change {
+    Created(Circle),
+    Resized { id: u32, from: u32, to: u32 },
+}
+
+fn nearest_circle<'a>(circles: &'a [Circle], point: &Point) -> Option<&'a Circle> {
+    circles
+        .iter()
+        .filter(|circle| circle.contains(point))
+        .min_by(|a, b| {
+            a.center
+                .distance_to(point)
+                .total_cmp(&b.center.distance_to(point))
+        })
+}
+
+fn view_head() -> maud::Markup {
+    layout::view_head(&registry::CIRCLE)
+}
+
+fn view_body(page_id: &browser::DomId, model: &Model) -> maud::Markup {
+    let can_undo = model.dialog.is_none() && !model.undo_stack.is_empty();
+    let can_redo = model.dialog.is_none() && !model.redo_stack.is_empty();
+
+    let content = html! {
+        div class="p-4" {
+            div {
+                (Button::primary(Id::Undo, "Undo").class("w-20").disabled(!can_undo))
+                (Button::primary(Id::Redo, "Redo").class("w-20 ml-4").disabled(!can_redo))
+            }
+
+            svg id=(Id::Canvas) width=(CANVAS_WIDTH) height=(CANVAS_HEIGHT) class="mt-4 border border-gray-400 bg-white" {
+                @for circle in &model.circles {
+                    circle cx=(circle.center.x) cy=(circle.center.y) r=(f64::from(circle.diameter) / 2.0) stroke="black" fill=(circle_fill(model, circle)) {}
+                }
+            }
+
+            @if let Some(dialog) = &model.dialog {
+                (view_dialog(model, dialog))
+            }
+        }
+    };
+
+    layout::view_body(page_id, &registry::CIRCLE, model.error.as_ref(), content)
+}
+
+fn view_dialog(model: &Model, dialog: &Dialog) -> maud::Markup {
+    let circle = model.circles.iter().find(|c| c.id == dialog.circle_id);
+
+    html! {
+        @if let Some(circle) = circle {
+            div class="mt-4 p-4 w-fit border border-gray-300 rounded-md shadow-sm" {
+                (Field::new(
+                    format!("Adjust diameter of circle at ({}, {}).", circle.center.x, circle.center.y),
+                    Input::range(Id::Diameter, MIN_DIAMETER.into(), MAX_DIAMETER.into(), circle.diameter).class("w-64"),
+                ))
+                (Button::primary(Id::CloseDialog, "Close").class("mt-4"))
+            }
+        }
+    }
+}
+
+fn circle_fill(model: &Model, circle: &Circle) -> &'static str {
+    if model.selected_id == Some(circle.id) {
+        "#d1d5db"
+    } else {
+        "white"
+    }
+}
@@ -0,0 +1,89 @@
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use polyester::time;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+use std::time::SystemTime;
+
+pub trait Clock {
+    fn now(&self) -> time::Posix;
+}
+
+// Reads the system time, which isn't available in the browser
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> time::Posix {
+        let millis = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+
+        time::Posix::from_millis(millis as i128)
+    }
+}
+
+// Only moves when told to. Clones share the same time, so a test can keep one and hand one to a page.
+#[derive(Clone)]
+pub struct ManualClock {
+    now: Rc<Cell<time::Posix>>,
+}
+
+impl ManualClock {
+    pub fn new(now: time::Posix) -> ManualClock {
+        ManualClock {
+            now: Rc::new(Cell::new(now)),
+        }
+    }
+
+    pub fn set(&self, now: time::Posix) {
+        self.now.set(now);
+    }
+
+    pub fn advance(&self, duration: Duration) -> time::Posix {
+        let now = time::Posix::from_millis(self.now().as_millis() + duration.as_millis() as i128);
+        self.set(now);
+        now
+    }
+
+    // Advances the clock by `interval` until `total` has passed, returning the time at every tick
+    pub fn ticks(&self, total: Duration, interval: Duration) -> Vec<time::Posix> {
+        let count = total.as_millis() / interval.as_millis().max(1);
+
+        (0..count).map(|_| self.advance(interval)).collect()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> time::Posix {
+        self.now.get()
+    }
+}
+
+// Accepts 2022-10-01T12:00:00Z, 2022-10-01T14:00:00+02:00, 2022-10-01T12:00:00 (as UTC) and 2022-10-01
+pub fn parse_iso8601(value: &str) -> Result<time::Posix, String> {
+    let millis = DateTime::parse_from_rfc3339(value)
+        .map(|date_time| date_time.timestamp_millis())
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+                .map(|date_time| date_time.timestamp_millis())
+                .ok()
+        })
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|date_time| date_time.timestamp_millis())
+        })
+        .ok_or_else(|| {
+            format!(
+                "Failed to parse time {}: expected ISO 8601, e.g. 2022-10-01T12:00:00Z",
+                value
+            )
+        })?;
+
+    Ok(time::Posix::from_millis(millis.into()))
+}
@@ -0,0 +1,371 @@
+use maud::html;
+use maud::Markup;
+use maud::Render;
+
+const BUTTON_PRIMARY_CLASS: &str = "text-center items-center px-3 py-2 border border-transparent text-sm leading-4 font-medium rounded-md shadow-sm text-white bg-indigo-600 hover:bg-indigo-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-indigo-500 disabled:opacity-50 disabled:cursor-not-allowed";
+
+const FIELD_CLASS: &str =
+    "shadow-sm block sm:text-sm rounded-md disabled:bg-gray-100 disabled:text-gray-500";
+const FIELD_VALID_CLASS: &str = "border-gray-300 focus:ring-indigo-500 focus:border-indigo-500";
+const FIELD_INVALID_CLASS: &str = "border-red-500 focus:ring-red-500 focus:border-red-500";
+
+const LABEL_CLASS: &str = "block text-sm font-medium text-gray-700";
+const ERROR_CLASS: &str = "mt-1 text-sm text-red-600";
+
+// Joins class lists, skipping the empty ones
+fn classes(parts: &[&str]) -> String {
+    parts
+        .iter()
+        .filter(|part| !part.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// A control that can be the target of a label
+pub trait Control: Render {
+    fn control_id(&self) -> &str;
+}
+
+pub struct Button {
+    id: String,
+    label: String,
+    class: String,
+    disabled: bool,
+}
+
+impl Button {
+    pub fn primary(id: impl ToString, label: impl ToString) -> Button {
+        Button {
+            id: id.to_string(),
+            label: label.to_string(),
+            class: String::new(),
+            disabled: false,
+        }
+    }
+
+    // Layout classes such as width and margin, styling belongs to the component
+    pub fn class(mut self, class: &str) -> Button {
+        self.class = class.to_string();
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Button {
+        self.disabled = disabled;
+        self
+    }
+}
+
+impl Render for Button {
+    fn render(&self) -> Markup {
+        html! {
+            button id=(self.id) class=(classes(&[&self.class, BUTTON_PRIMARY_CLASS])) type="button" disabled[self.disabled] {
+                (self.label)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum InputKind {
+    Text,
+    Number,
+    Date,
+    Range { min: i64, max: i64 },
+    File,
+}
+
+impl InputKind {
+    fn type_name(&self) -> &'static str {
+        match self {
+            InputKind::Text => "text",
+            InputKind::Number => "number",
+            InputKind::Date => "date",
+            InputKind::Range { .. } => "range",
+            InputKind::File => "file",
+        }
+    }
+
+    // Sliders and file pickers keep most of the browser's own look
+    fn is_field(&self) -> bool {
+        matches!(self, InputKind::Text | InputKind::Number | InputKind::Date)
+    }
+}
+
+pub struct Input {
+    id: String,
+    kind: InputKind,
+    value: Option<String>,
+    class: String,
+    accept: Option<String>,
+    disabled: bool,
+    invalid: bool,
+    readonly: bool,
+    autofocus: bool,
+}
+
+impl Input {
+    fn new(id: impl ToString, kind: InputKind, value: Option<String>) -> Input {
+        Input {
+            id: id.to_string(),
+            kind,
+            value,
+            class: String::new(),
+            accept: None,
+            disabled: false,
+            invalid: false,
+            readonly: false,
+            autofocus: false,
+        }
+    }
+
+    pub fn text(id: impl ToString, value: impl ToString) -> Input {
+        Input::new(id, InputKind::Text, Some(value.to_string()))
+    }
+
+    pub fn number(id: impl ToString, value: impl ToString) -> Input {
+        Input::new(id, InputKind::Number, Some(value.to_string()))
+    }
+
+    pub fn date(id: impl ToString, value: impl ToString) -> Input {
+        Input::new(id, InputKind::Date, Some(value.to_string()))
+    }
+
+    pub fn range(id: impl ToString, min: i64, max: i64, value: impl ToString) -> Input {
+        Input::new(id, InputKind::Range { min, max }, Some(value.to_string()))
+    }
+
+    pub fn file(id: impl ToString, accept: &str) -> Input {
+        Input {
+            accept: Some(accept.to_string()),
+            ..Input::new(id, InputKind::File, None)
+        }
+    }
+
+    pub fn class(mut self, class: &str) -> Input {
+        self.class = class.to_string();
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Input {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn invalid(mut self, invalid: bool) -> Input {
+        self.invalid = invalid;
+        self
+    }
+
+    pub fn readonly(mut self) -> Input {
+        self.readonly = true;
+        self
+    }
+
+    pub fn autofocus(mut self) -> Input {
+        self.autofocus = true;
+        self
+    }
+
+    fn class_list(&self) -> String {
+        if self.kind.is_field() {
+            let state = if self.invalid {
+                FIELD_INVALID_CLASS
+            } else {
+                FIELD_VALID_CLASS
+            };
+
+            classes(&[&self.class, FIELD_CLASS, state])
+        } else if self.kind == InputKind::File {
+            classes(&[&self.class, "text-sm"])
+        } else {
+            self.class.clone()
+        }
+    }
+}
+
+impl Render for Input {
+    fn render(&self) -> Markup {
+        let class = self.class_list();
+
+        html! {
+            @match (self.kind, &self.value) {
+                (InputKind::Range { min, max }, Some(value)) => {
+                    input id=(self.id) type=(self.kind.type_name()) min=(min) max=(max) value=(value) class=(class) disabled[self.disabled];
+                }
+
+                (InputKind::File, _) => {
+                    input id=(self.id) type=(self.kind.type_name()) accept=(self.accept.as_deref().unwrap_or_default()) class=(class) disabled[self.disabled];
+                }
+
+                (_, value) => {
+                    input id=(self.id) type=(self.kind.type_name()) value=(value.as_deref().unwrap_or_default()) class=(class) disabled[self.disabled] readonly[self.readonly] autofocus[self.autofocus] aria-invalid=(self.invalid);
+                }
+            }
+        }
+    }
+}
+
+impl Control for Input {
+    fn control_id(&self) -> &str {
+        &self.id
+    }
+}
+
+pub struct Select {
+    id: String,
+    options: Vec<(String, String, bool)>,
+    size: Option<usize>,
+    class: String,
+    disabled: bool,
+}
+
+impl Select {
+    pub fn new(id: impl ToString) -> Select {
+        Select {
+            id: id.to_string(),
+            options: vec![],
+            size: None,
+            class: String::new(),
+            disabled: false,
+        }
+    }
+
+    pub fn option(mut self, value: impl ToString, label: impl ToString, selected: bool) -> Select {
+        self.options
+            .push((value.to_string(), label.to_string(), selected));
+        self
+    }
+
+    // Shows this many rows at once, as a list box rather than a dropdown
+    pub fn size(mut self, size: usize) -> Select {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn class(mut self, class: &str) -> Select {
+        self.class = class.to_string();
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Select {
+        self.disabled = disabled;
+        self
+    }
+}
+
+impl Render for Select {
+    fn render(&self) -> Markup {
+        let class = classes(&[&self.class, FIELD_CLASS, FIELD_VALID_CLASS]);
+        let options = html! {
+            @for (value, label, selected) in &self.options {
+                option value=(value) selected[*selected] { (label) }
+            }
+        };
+
+        html! {
+            @if let Some(size) = self.size {
+                select id=(self.id) size=(size) class=(class) disabled[self.disabled] {
+                    (options)
+                }
+            } @else {
+                select id=(self.id) class=(class) disabled[self.disabled] {
+                    (options)
+                }
+            }
+        }
+    }
+}
+
+impl Control for Select {
+    fn control_id(&self) -> &str {
+        &self.id
+    }
+}
+
+pub struct Label {
+    for_id: String,
+    text: String,
+}
+
+impl Label {
+    pub fn new(for_id: impl ToString, text: impl ToString) -> Label {
+        Label {
+            for_id: for_id.to_string(),
+            text: text.to_string(),
+        }
+    }
+}
+
+impl Render for Label {
+    fn render(&self) -> Markup {
+        html! {
+            label class=(LABEL_CLASS) for=(self.for_id) { (self.text) }
+        }
+    }
+}
+
+pub struct ErrorMessage {
+    message: String,
+}
+
+impl ErrorMessage {
+    pub fn new(message: impl ToString) -> ErrorMessage {
+        ErrorMessage {
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Render for ErrorMessage {
+    fn render(&self) -> Markup {
+        html! {
+            div class=(ERROR_CLASS) role="alert" { (self.message) }
+        }
+    }
+}
+
+// A label above its control, with an optional error below it
+pub struct Field<C> {
+    label: String,
+    control: C,
+    error: Option<String>,
+    class: String,
+}
+
+impl<C: Control> Field<C> {
+    pub fn new(label: impl ToString, control: C) -> Field<C> {
+        Field {
+            label: label.to_string(),
+            control,
+            error: None,
+            class: String::new(),
+        }
+    }
+
+    pub fn error(mut self, error: Option<impl ToString>) -> Field<C> {
+        self.error = error.map(|error| error.to_string());
+        self
+    }
+
+    pub fn class(mut self, class: &str) -> Field<C> {
+        self.class = class.to_string();
+        self
+    }
+}
+
+impl<C: Control> Render for Field<C> {
+    fn render(&self) -> Markup {
+        html! {
+            div class=(self.class) {
+                (Label::new(self.control.control_id(), &self.label))
+                div class="mt-1" {
+                    (self.control)
+                }
+                @if let Some(error) = &self.error {
+                    (ErrorMessage::new(error))
+                }
+            }
+        }
+    }
+}
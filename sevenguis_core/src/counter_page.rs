@@ -1,3 +1,11 @@
+use crate::components::Button;
+use crate::components::Input;
+use crate::history;
+use crate::history::History;
+use crate::history::Undoable;
+use crate::hydration::Validate;
+use crate::layout;
+use crate::registry;
 use maud::html;
 use polyester::browser;
 use polyester::browser::DomId;
@@ -11,6 +19,25 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub struct Model {
     pub count: isize,
+    pub history: History<isize>,
+}
+
+impl Validate for Model {}
+
+impl Undoable for Model {
+    type Snapshot = isize;
+
+    fn history(&mut self) -> &mut History<isize> {
+        &mut self.history
+    }
+
+    fn snapshot(&self) -> isize {
+        self.count
+    }
+
+    fn restore(&mut self, count: isize) {
+        self.count = count;
+    }
 }
 
 pub struct CounterPage {}
@@ -21,7 +48,10 @@ impl Page<Model, Msg, AppEffect> for CounterPage {
     }
 
     fn init(&self) -> (Model, Effects<Msg, AppEffect>) {
-        let model = Model { count: 0 };
+        let model = Model {
+            count: 0,
+            history: History::new(history::DEFAULT_LIMIT),
+        };
 
         let effects = vec![];
 
@@ -32,20 +62,34 @@ impl Page<Model, Msg, AppEffect> for CounterPage {
         vec![
             browser::on_click(&Id::Increment, Msg::Increment),
             browser::on_click(&Id::Decrement, Msg::Decrement),
+            browser::on_click(&Id::Undo, Msg::Undo),
+            browser::on_click(&Id::Redo, Msg::Redo),
         ]
     }
 
     fn update(&self, msg: &Msg, model: &mut Model) -> Result<Effects<Msg, AppEffect>, String> {
         match msg {
             Msg::Increment => {
+                model.record();
                 model.count += 1;
                 Ok(vec![])
             }
 
             Msg::Decrement => {
+                model.record();
                 model.count -= 1;
                 Ok(vec![])
             }
+
+            Msg::Undo => {
+                model.undo();
+                Ok(vec![])
+            }
+
+            Msg::Redo => {
+                model.redo();
+                Ok(vec![])
+            }
         }
     }
 
@@ -59,9 +103,12 @@ impl Page<Model, Msg, AppEffect> for CounterPage {
 
 #[derive(strum_macros::Display, polyester_macro::ToDomId)]
 #[strum(serialize_all = "kebab-case")]
-enum Id {
+pub enum Id {
+    Count,
     Increment,
     Decrement,
+    Undo,
+    Redo,
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
@@ -69,6 +116,8 @@ enum Id {
 pub enum Msg {
     Increment,
     Decrement,
+    Undo,
+    Redo,
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
@@ -76,27 +125,25 @@ pub enum Msg {
 pub enum AppEffect {}
 
 fn view_head() -> maud::Markup {
-    html! {
-        title { "Home page" }
-        link rel="stylesheet" href="/app.css";
-        script defer type="module" src="/counter_page.js" {}
-    }
+    layout::view_head(&registry::COUNTER)
 }
 
 fn view_body(page_id: &browser::DomId, model: &Model) -> maud::Markup {
-    html! {
-        div id=(page_id) {
+    let content = html! {
+        div {
             div class="flex p-4" {
-                button id=(Id::Decrement) class="w-28 text-center items-center px-3 py-2 border border-transparent text-sm leading-4 font-medium rounded-md shadow-sm text-white bg-indigo-600 hover:bg-indigo-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-indigo-500" type="button" {
-                    "Decrement"
-                }
+                (Button::primary(Id::Decrement, "Decrement").class("w-28"))
                 div class="mx-4 w-28" {
-                    input value=(model.count) class="text-center shadow-sm focus:ring-indigo-500 focus:border-indigo-500 block w-full sm:text-sm border-gray-300 rounded-md" type="text" readonly;
-                }
-                button id=(Id::Increment) class="w-28 text-center items-center px-3 py-2 border border-transparent text-sm leading-4 font-medium rounded-md shadow-sm text-white bg-indigo-600 hover:bg-indigo-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-indigo-500" type="button" {
-                    "Increment"
+                    (Input::text(Id::Count, model.count).class("w-full text-center").readonly())
                 }
+                (Button::primary(Id::Increment, "Increment").class("w-28"))
+            }
+            div class="flex px-4" {
+                (Button::primary(Id::Undo, "Undo").class("w-28").disabled(!model.history.can_undo()))
+                (Button::primary(Id::Redo, "Redo").class("w-28 ml-4").disabled(!model.history.can_redo()))
             }
         }
-    }
+    };
+
+    layout::view_body(page_id, &registry::COUNTER, None, content)
 }
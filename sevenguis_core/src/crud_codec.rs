@@ -0,0 +1,394 @@
+use crate::crud_page::validate_name;
+use crate::crud_page::Person;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    pub fn from_filename(filename: &str) -> Option<Format> {
+        let (_, extension) = filename.rsplit_once('.')?;
+        extension.parse().ok()
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Json => "json",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Format::Csv => "text/csv",
+            Format::Json => "application/json",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportMode {
+    Merge,
+    Replace,
+}
+
+impl FromStr for ImportMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ImportMode, String> {
+        match s.to_lowercase().as_str() {
+            "merge" => Ok(ImportMode::Merge),
+            "replace" => Ok(ImportMode::Replace),
+            _ => Err(format!("Unknown import mode: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RowError {
+    pub row: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Row {}: {}", self.row, self.message)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub id: Option<u32>,
+    pub first_name: String,
+    pub last_name: String,
+}
+
+pub struct Decoded {
+    // Valid records, with the row each one came from
+    pub records: Vec<(usize, Record)>,
+    pub errors: Vec<RowError>,
+}
+
+pub struct Imported {
+    pub people: Vec<Person>,
+    pub next_id: u32,
+    // How many people were added, merged duplicates are skipped
+    pub imported: usize,
+    pub errors: Vec<RowError>,
+}
+
+pub fn encode(people: &[Person], format: Format) -> String {
+    match format {
+        Format::Csv => encode_csv(people),
+        Format::Json => serde_json::to_string_pretty(people).unwrap_or_default() + "\n",
+    }
+}
+
+// Fails only when the file as a whole is unreadable, invalid rows are reported in `errors`
+pub fn decode(s: &str, format: Format) -> Result<Decoded, String> {
+    let rows = match format {
+        Format::Csv => decode_csv(s)?,
+        Format::Json => decode_json(s)?,
+    };
+
+    let mut records = vec![];
+    let mut errors = vec![];
+
+    for (row, result) in rows {
+        match result.and_then(validate_record) {
+            Ok(record) => records.push((row, record)),
+            Err(message) => errors.push(RowError { row, message }),
+        }
+    }
+
+    Ok(Decoded { records, errors })
+}
+
+// Combines the decoded records with the current people, `next_id` is the current next free id.
+// An id of u32::MAX is never handed out, a next id of u32::MAX means the ids have run out.
+pub fn apply(people: &[Person], next_id: u32, decoded: Decoded, mode: ImportMode) -> Imported {
+    let mut result: Vec<Person> = match mode {
+        ImportMode::Merge => people.to_vec(),
+        ImportMode::Replace => vec![],
+    };
+
+    let mut used_ids: HashSet<u32> = result.iter().map(|p| p.id).collect();
+    let mut errors = decoded.errors;
+    let mut imported = 0;
+    let mut pending = vec![];
+
+    // Names seen so far, from the existing people and from earlier rows of the file
+    let mut names: HashSet<(String, String)> = result
+        .iter()
+        .map(|p| (p.first_name.clone(), p.last_name.clone()))
+        .collect();
+
+    for (row, record) in decoded.records {
+        let is_duplicate = !names.insert((record.first_name.clone(), record.last_name.clone()));
+
+        if mode == ImportMode::Merge && is_duplicate {
+            continue;
+        }
+
+        // Ids from the file are only kept when replacing, merged people always get fresh ids
+        match record.id {
+            Some(id)
+                if mode == ImportMode::Replace
+                    && id > 0
+                    && id < u32::MAX
+                    && used_ids.insert(id) =>
+            {
+                result.push(Person {
+                    id,
+                    first_name: record.first_name,
+                    last_name: record.last_name,
+                });
+
+                imported += 1;
+            }
+
+            _ => {
+                pending.push((row, record));
+            }
+        }
+    }
+
+    // Merging must not hand out the ids of people deleted since
+    let mut next_id = match mode {
+        ImportMode::Merge => next_free_id(&result).max(next_id),
+        ImportMode::Replace => next_free_id(&result),
+    };
+
+    for (row, record) in pending {
+        match next_id.checked_add(1) {
+            Some(following) => {
+                result.push(Person {
+                    id: next_id,
+                    first_name: record.first_name,
+                    last_name: record.last_name,
+                });
+
+                next_id = following;
+                imported += 1;
+            }
+
+            None => errors.push(RowError {
+                row,
+                message: "No ids left for new people".to_string(),
+            }),
+        }
+    }
+
+    errors.sort_by_key(|err| err.row);
+
+    Imported {
+        people: result,
+        next_id,
+        imported,
+        errors,
+    }
+}
+
+pub fn next_free_id(people: &[Person]) -> u32 {
+    people
+        .iter()
+        .map(|p| p.id)
+        .max()
+        .unwrap_or(0)
+        .saturating_add(1)
+}
+
+fn validate_record(record: Record) -> Result<Record, String> {
+    let first_name =
+        validate_name(&record.first_name).map_err(|err| format!("First name: {}", err))?;
+    let last_name =
+        validate_name(&record.last_name).map_err(|err| format!("Last name: {}", err))?;
+
+    Ok(Record {
+        id: record.id,
+        first_name,
+        last_name,
+    })
+}
+
+type Row = (usize, Result<Record, String>);
+
+fn decode_json(s: &str) -> Result<Vec<Row>, String> {
+    let values: Vec<serde_json::Value> =
+        serde_json::from_str(s).map_err(|err| format!("Invalid json: {}", err))?;
+
+    let rows = values
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let record = serde_json::from_value(value).map_err(|err| err.to_string());
+            (index + 1, record)
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+const CSV_HEADER: [&str; 3] = ["id", "first_name", "last_name"];
+
+fn encode_csv(people: &[Person]) -> String {
+    let mut lines = vec![CSV_HEADER.join(",")];
+
+    for person in people {
+        let fields = [
+            person.id.to_string(),
+            csv_field(&person.first_name),
+            csv_field(&person.last_name),
+        ];
+
+        lines.push(fields.join(","));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn decode_csv(s: &str) -> Result<Vec<Row>, String> {
+    let mut lines = parse_csv(s)?.into_iter();
+
+    let (_, header) = lines.next().ok_or("The file is empty")?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+
+    let id_column = column("id");
+    let first_name_column = column("first_name").ok_or("Missing first_name column")?;
+    let last_name_column = column("last_name").ok_or("Missing last_name column")?;
+
+    let rows = lines
+        .filter(|(_, fields)| !(fields.len() == 1 && fields[0].trim().is_empty()))
+        .map(|(line, fields)| {
+            let record = csv_record(&fields, id_column, first_name_column, last_name_column);
+            (line, record)
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+fn csv_record(
+    fields: &[String],
+    id_column: Option<usize>,
+    first_name_column: usize,
+    last_name_column: usize,
+) -> Result<Record, String> {
+    let field = |index: usize| {
+        fields
+            .get(index)
+            .cloned()
+            .ok_or_else(|| format!("Missing column {}", index + 1))
+    };
+
+    let id = match id_column.map(field).transpose()? {
+        Some(id) if !id.trim().is_empty() => {
+            let id = id
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid id: {}", id))?;
+            Some(id)
+        }
+
+        _ => None,
+    };
+
+    Ok(Record {
+        id,
+        first_name: field(first_name_column)?,
+        last_name: field(last_name_column)?,
+    })
+}
+
+// Splits csv into records of fields, keeping the line number each record starts on
+fn parse_csv(s: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let mut records = vec![];
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+
+            ('"', false) if field.is_empty() => {
+                in_quotes = true;
+            }
+
+            (',', false) => {
+                fields.push(std::mem::take(&mut field));
+            }
+
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+
+            ('\n', false) => {
+                fields.push(std::mem::take(&mut field));
+                records.push((record_line, std::mem::take(&mut fields)));
+                line += 1;
+                record_line = line;
+            }
+
+            (c, _) => {
+                if c == '\n' {
+                    line += 1;
+                }
+
+                field.push(c);
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(format!("Unterminated quoted field on line {}", record_line));
+    }
+
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        records.push((record_line, fields));
+    }
+
+    Ok(records)
+}
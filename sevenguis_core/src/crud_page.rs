@@ -1,5 +1,24 @@
+use crate::components::Button;
+use crate::components::Field;
+use crate::components::Input;
+use crate::components::Select;
+use crate::crud_codec;
+use crate::crud_codec::Format;
+use crate::crud_codec::ImportMode;
+use crate::history;
+use crate::history::History;
+use crate::history::Undoable;
+use crate::hydration;
+use crate::hydration::Validate;
+use crate::layout;
+use crate::page_error;
+use crate::page_error::ErrorState;
+use crate::page_error::PageError;
+use crate::registry;
+use crate::runtime;
 use maud::html;
 use polyester::browser;
+use polyester::browser::to_value;
 use polyester::browser::DomId;
 use polyester::browser::Effects;
 use polyester::browser::ToDomId;
@@ -7,10 +26,11 @@ use polyester::browser::Value;
 use polyester::page::Page;
 use polyester::page::PageMarkup;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(strum_macros::Display, polyester_macro::ToDomId)]
 #[strum(serialize_all = "kebab-case")]
-enum Id {
+pub enum Id {
     Filter,
     FirstName,
     LastName,
@@ -18,9 +38,16 @@ enum Id {
     Create,
     Update,
     Delete,
+    Discard,
+    Undo,
+    Redo,
+    ExportCsv,
+    ExportJson,
+    ImportFile,
+    ImportMode,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
     pub next_id: u32,
@@ -28,6 +55,118 @@ pub struct Model {
     pub people: Vec<Person>,
     pub form: Form,
     pub filter: String,
+    pub history: History<Snapshot>,
+    pub import_mode: ImportMode,
+    pub import_result: Option<ImportResult>,
+    pub error: Option<PageError>,
+}
+
+impl Validate for Model {
+    fn validate(&self) -> Result<(), String> {
+        hydration::validate_ids(self.people.iter().map(|p| p.id), self.next_id)?;
+
+        // The selection can't be hidden, same as after any update
+        if self.selected_id != 0 && self.selected_person().is_none() {
+            return Err(format!("Selected person {} isn't shown", self.selected_id));
+        }
+
+        Ok(())
+    }
+}
+
+impl ErrorState for Model {
+    fn error(&self) -> Option<&PageError> {
+        self.error.as_ref()
+    }
+
+    fn set_error(&mut self, error: Option<PageError>) {
+        self.error = error;
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResult {
+    pub imported: usize,
+    pub errors: Vec<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    next_id: u32,
+    selected_id: u32,
+    people: Vec<Person>,
+}
+
+impl Undoable for Model {
+    type Snapshot = Snapshot;
+
+    fn history(&mut self) -> &mut History<Snapshot> {
+        &mut self.history
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            next_id: self.next_id,
+            selected_id: self.selected_id,
+            people: self.people.clone(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.next_id = snapshot.next_id;
+        self.selected_id = snapshot.selected_id;
+        self.people = snapshot.people;
+        self.reset_form();
+    }
+}
+
+impl Model {
+    fn is_visible(&self, person: &Person) -> bool {
+        self.filter.is_empty() || person.last_name.to_lowercase().starts_with(&self.filter)
+    }
+
+    fn filtered_people(&self) -> Vec<&Person> {
+        self.people
+            .iter()
+            .filter(|person| self.is_visible(person))
+            .collect()
+    }
+
+    // The selected person, as long as the filter doesn't hide it
+    fn selected_person(&self) -> Option<&Person> {
+        self.people
+            .iter()
+            .find(|person| person.id == self.selected_id)
+            .filter(|person| self.is_visible(person))
+    }
+
+    fn select(&mut self, id: u32) {
+        self.selected_id = id;
+        self.reset_form();
+    }
+
+    fn select_first_visible(&mut self) {
+        let id = self.filtered_people().first().map(|p| p.id).unwrap_or(0);
+        self.select(id);
+    }
+
+    // Whether someone, other than the person being updated, already has these exact names
+    fn is_duplicate(&self, first_name: &str, last_name: &str, updating: Option<u32>) -> bool {
+        self.people.iter().any(|person| {
+            Some(person.id) != updating
+                && person.first_name == first_name
+                && person.last_name == last_name
+        })
+    }
+
+    fn reset_form(&mut self) {
+        self.form = self
+            .selected_person()
+            .map(Form::from_person)
+            .unwrap_or_else(Form::empty);
+    }
 }
 
 pub struct CrudPage {}
@@ -38,34 +177,21 @@ impl Page<Model, Msg, AppEffect> for CrudPage {
     }
 
     fn init(&self) -> (Model, Effects<Msg, AppEffect>) {
-        let model = Model {
+        let mut model = Model {
             next_id: 4,
-            selected_id: 1,
-            people: vec![
-                Person {
-                    id: 1,
-                    first_name: "Hans".to_string(),
-                    last_name: "Emil".to_string(),
-                },
-                Person {
-                    id: 2,
-                    first_name: "Max".to_string(),
-                    last_name: "Mustermann".to_string(),
-                },
-                Person {
-                    id: 3,
-                    first_name: "Roman".to_string(),
-                    last_name: "Tisch".to_string(),
-                },
-            ],
-            form: Form {
-                first_name: "".to_string(),
-                last_name: "".to_string(),
-            },
+            selected_id: 0,
+            people: seed_people(),
+            form: Form::empty(),
             filter: "".to_string(),
+            history: History::new(history::DEFAULT_LIMIT),
+            import_mode: ImportMode::Merge,
+            import_result: None,
+            error: None,
         };
 
-        let effects = vec![];
+        model.select(1);
+
+        let effects = vec![browser::app_effect(AppEffect::LoadPeople)];
 
         (model, effects)
     }
@@ -79,26 +205,67 @@ impl Page<Model, Msg, AppEffect> for CrudPage {
             browser::on_click(&Id::Create, Msg::Create),
             browser::on_click(&Id::Update, Msg::Update),
             browser::on_click(&Id::Delete, Msg::Delete),
+            browser::on_click(&Id::Discard, Msg::Discard),
+            browser::on_click(&Id::Undo, Msg::Undo),
+            browser::on_click(&Id::Redo, Msg::Redo),
+            browser::on_click(&Id::ExportCsv, Msg::Export(Format::Csv)),
+            browser::on_click(&Id::ExportJson, Msg::Export(Format::Json)),
+            browser::on_change(&Id::ImportMode, Msg::ImportModeChanged),
+            browser::on_click(&page_error::Id::DismissError, Msg::ErrorDismissed),
         ]
     }
 
     fn update(&self, msg: &Msg, model: &mut Model) -> Result<Effects<Msg, AppEffect>, String> {
+        runtime::update(model, |model| self.try_update(msg, model))
+    }
+
+    fn view(&self, model: &Model) -> PageMarkup {
+        PageMarkup {
+            head: view_head(),
+            body: view_body(&self.id(), model),
+        }
+    }
+}
+
+impl CrudPage {
+    fn try_update(
+        &self,
+        msg: &Msg,
+        model: &mut Model,
+    ) -> Result<Effects<Msg, AppEffect>, PageError> {
         match msg {
+            Msg::PeopleLoaded(data) => {
+                // Missing or corrupt data leaves the seed data in place
+                if let Some(stored) = data.as_deref().and_then(|s| StoredPeople::decode(s).ok()) {
+                    model.next_id = stored.next_id;
+                    model.people = stored.people;
+                    model.select_first_visible();
+                }
+
+                Ok(vec![])
+            }
+
             Msg::FilterChanged(value) => {
                 let filter: String = value
                     .parse()
-                    .map_err(|err| format!("Failed to parse filter: {}", err))?;
+                    .map_err(|err| PageError::parse("filter", err))?;
 
                 model.filter = filter.to_lowercase();
+
+                // Update and Delete act on the selection, so it can't stay hidden
+                if model.selected_person().is_none() {
+                    model.select_first_visible();
+                }
+
                 Ok(vec![])
             }
 
             Msg::SelectedPersonChanged(value) => {
                 let selected_id = value
                     .parse()
-                    .map_err(|err| format!("Failed to parse selected person: {}", err))?;
+                    .map_err(|err| PageError::parse("selected person", err))?;
 
-                model.selected_id = selected_id;
+                model.select(selected_id);
 
                 Ok(vec![])
             }
@@ -106,7 +273,7 @@ impl Page<Model, Msg, AppEffect> for CrudPage {
             Msg::FirstNameChanged(value) => {
                 let first_name = value
                     .parse()
-                    .map_err(|err| format!("Failed to parse first name: {}", err))?;
+                    .map_err(|err| PageError::parse("first name", err))?;
 
                 model.form.first_name = first_name;
 
@@ -116,7 +283,7 @@ impl Page<Model, Msg, AppEffect> for CrudPage {
             Msg::LastNameChanged(value) => {
                 let last_name = value
                     .parse()
-                    .map_err(|err| format!("Failed to parse last name: {}", err))?;
+                    .map_err(|err| PageError::parse("last name", err))?;
 
                 model.form.last_name = last_name;
 
@@ -124,47 +291,154 @@ impl Page<Model, Msg, AppEffect> for CrudPage {
             }
 
             Msg::Create => {
-                model.people.push(Person {
-                    id: model.next_id,
-                    first_name: model.form.first_name.clone(),
-                    last_name: model.form.last_name.clone(),
-                });
+                let id = model.next_id;
+                let next_id = id.checked_add(1).ok_or_else(|| {
+                    PageError::invalid_state("create", "No ids left for new people")
+                })?;
 
-                model.form = Form::empty();
-                model.selected_id = model.next_id;
-                model.next_id += 1;
+                let person = model.form.validate().to_person(id).ok_or_else(|| {
+                    PageError::invalid_state("create", "Can't create person from invalid form")
+                })?;
 
-                Ok(vec![])
+                model.record();
+                model.people.push(person);
+                model.next_id = next_id;
+                model.select(id);
+
+                Ok(vec![save_people(model)])
             }
 
             Msg::Update => {
-                model
-                    .people
-                    .iter_mut()
-                    .find(|person| person.id == model.selected_id)
-                    .map(|person| {
-                        person.first_name = model.form.first_name.clone();
-                        person.last_name = model.form.last_name.clone();
-                    });
+                let selected_id =
+                    model
+                        .selected_person()
+                        .map(|person| person.id)
+                        .ok_or_else(|| {
+                            PageError::invalid_state("update", "No visible person is selected")
+                        })?;
+
+                let updated_person =
+                    model
+                        .form
+                        .validate()
+                        .to_person(selected_id)
+                        .ok_or_else(|| {
+                            PageError::invalid_state(
+                                "update",
+                                "Can't update person from invalid form",
+                            )
+                        })?;
+
+                model.record();
+
+                if let Some(person) = model.people.iter_mut().find(|p| p.id == selected_id) {
+                    *person = updated_person;
+                }
 
-                model.form = Form::empty();
+                // The new names may no longer match the filter
+                if model.selected_person().is_some() {
+                    model.reset_form();
+                } else {
+                    model.select_first_visible();
+                }
 
-                Ok(vec![])
+                Ok(vec![save_people(model)])
             }
 
             Msg::Delete => {
-                model.people.retain(|p| p.id != model.selected_id);
-                model.selected_id = model.people.first().map(|p| p.id).unwrap_or(0);
+                let selected_id =
+                    model
+                        .selected_person()
+                        .map(|person| person.id)
+                        .ok_or_else(|| {
+                            PageError::invalid_state("delete", "No visible person is selected")
+                        })?;
+
+                model.record();
+                model.people.retain(|p| p.id != selected_id);
+                model.select_first_visible();
+
+                Ok(vec![save_people(model)])
+            }
+
+            Msg::Discard => {
+                model.form.discard();
 
                 Ok(vec![])
             }
-        }
-    }
 
-    fn view(&self, model: &Model) -> PageMarkup {
-        PageMarkup {
-            head: view_head(),
-            body: view_body(&self.id(), model),
+            Msg::Export(format) => {
+                let effect = browser::app_effect(AppEffect::Download {
+                    filename: format!("people.{}", format.extension()),
+                    mime_type: format.mime_type().to_string(),
+                    content: crud_codec::encode(&model.people, *format),
+                });
+
+                Ok(vec![effect])
+            }
+
+            Msg::ImportModeChanged(value) => {
+                let import_mode = value
+                    .parse()
+                    .map_err(|err| PageError::parse("import mode", err))?;
+
+                model.import_mode = import_mode;
+
+                Ok(vec![])
+            }
+
+            Msg::FileImported(file) => {
+                let decoded = Format::from_filename(&file.name)
+                    .ok_or_else(|| format!("Unsupported file type: {}", file.name))
+                    .and_then(|format| crud_codec::decode(&file.content, format));
+
+                match decoded {
+                    Ok(decoded) => {
+                        let imported = crud_codec::apply(
+                            &model.people,
+                            model.next_id,
+                            decoded,
+                            model.import_mode,
+                        );
+
+                        model.record();
+                        model.people = imported.people;
+                        model.next_id = imported.next_id;
+                        model.select_first_visible();
+
+                        model.import_result = Some(ImportResult {
+                            imported: imported.imported,
+                            errors: imported.errors.iter().map(|err| err.to_string()).collect(),
+                        });
+
+                        Ok(vec![save_people(model)])
+                    }
+
+                    Err(err) => {
+                        model.import_result = Some(ImportResult {
+                            imported: 0,
+                            errors: vec![err],
+                        });
+
+                        Ok(vec![])
+                    }
+                }
+            }
+
+            Msg::Undo => {
+                model.undo();
+                Ok(vec![save_people(model)])
+            }
+
+            Msg::Redo => {
+                model.redo();
+                Ok(vec![save_people(model)])
+            }
+
+            Msg::ErrorDismissed => {
+                model.error = None;
+                Ok(vec![])
+            }
         }
     }
 }
@@ -172,6 +446,7 @@ impl Page<Model, Msg, AppEffect> for CrudPage {
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Msg {
+    PeopleLoaded(Option<String>),
     FilterChanged(String),
     SelectedPersonChanged(Value),
     FirstNameChanged(String),
@@ -179,23 +454,123 @@ pub enum Msg {
     Create,
     Update,
     Delete,
+    Discard,
+    Undo,
+    Redo,
+    Export(Format),
+    ImportModeChanged(Value),
+    FileImported(ImportedFile),
+    ErrorDismissed,
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub enum AppEffect {}
+pub struct ImportedFile {
+    pub name: String,
+    pub content: String,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AppEffect {
+    LoadPeople,
+    SavePeople(StoredPeople),
+    #[serde(rename_all = "camelCase")]
+    Download {
+        filename: String,
+        mime_type: String,
+        content: String,
+    },
+}
+
+const STORAGE_VERSION: u32 = 1;
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredPeople {
+    pub version: u32,
+    pub next_id: u32,
+    pub people: Vec<Person>,
+}
+
+impl StoredPeople {
+    pub fn new(next_id: u32, people: Vec<Person>) -> StoredPeople {
+        StoredPeople {
+            version: STORAGE_VERSION,
+            next_id,
+            people,
+        }
+    }
+
+    fn from_model(model: &Model) -> StoredPeople {
+        StoredPeople::new(model.next_id, model.people.clone())
+    }
+
+    pub fn decode(s: &str) -> Result<StoredPeople, String> {
+        let stored: StoredPeople =
+            serde_json::from_str(s).map_err(|err| format!("Failed to decode people: {}", err))?;
+
+        if stored.version != STORAGE_VERSION {
+            return Err(format!("Unsupported storage version: {}", stored.version));
+        }
+
+        let mut ids: Vec<u32> = stored.people.iter().map(|p| p.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+
+        if ids.len() != stored.people.len() {
+            return Err("Stored people contain duplicate ids".to_string());
+        }
+
+        let max_id = ids.last().copied().unwrap_or(0);
+        let min_next_id = max_id
+            .checked_add(1)
+            .ok_or_else(|| "Stored people leave no id for the next person".to_string())?;
+
+        Ok(StoredPeople {
+            next_id: stored.next_id.max(min_next_id),
+            ..stored
+        })
+    }
+}
+
+fn save_people(model: &Model) -> browser::Effect<Msg, AppEffect> {
+    browser::app_effect(AppEffect::SavePeople(StoredPeople::from_model(model)))
+}
+
+pub fn seed_people() -> Vec<Person> {
+    vec![
+        Person {
+            id: 1,
+            first_name: "Hans".to_string(),
+            last_name: "Emil".to_string(),
+        },
+        Person {
+            id: 2,
+            first_name: "Max".to_string(),
+            last_name: "Mustermann".to_string(),
+        },
+        Person {
+            id: 3,
+            first_name: "Roman".to_string(),
+            last_name: "Tisch".to_string(),
+        },
+    ]
+}
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct Person {
-    id: u32,
-    first_name: String,
-    last_name: String,
+    pub id: u32,
+    pub first_name: String,
+    pub last_name: String,
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct Form {
     first_name: String,
     last_name: String,
+    original_first_name: String,
+    original_last_name: String,
 }
 
 impl Form {
@@ -203,67 +578,188 @@ impl Form {
         Form {
             first_name: "".to_string(),
             last_name: "".to_string(),
+            original_first_name: "".to_string(),
+            original_last_name: "".to_string(),
+        }
+    }
+
+    pub fn from_person(person: &Person) -> Form {
+        Form {
+            first_name: person.first_name.clone(),
+            last_name: person.last_name.clone(),
+            original_first_name: person.first_name.clone(),
+            original_last_name: person.last_name.clone(),
+        }
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.first_name != self.original_first_name || self.last_name != self.original_last_name
+    }
+
+    pub fn discard(&mut self) {
+        self.first_name = self.original_first_name.clone();
+        self.last_name = self.original_last_name.clone();
+    }
+
+    pub fn validate(&self) -> Validation {
+        Validation {
+            first_name: validate_name(&self.first_name),
+            last_name: validate_name(&self.last_name),
         }
     }
 }
 
-fn view_head() -> maud::Markup {
-    html! {
-        title { "Crud" }
-        link rel="stylesheet" href="/app.css";
-        script defer type="module" src="/crud_page.js" {}
+const MAX_NAME_LENGTH: usize = 50;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum NameError {
+    Required,
+    TooLong,
+}
+
+impl fmt::Display for NameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NameError::Required => write!(f, "Required"),
+            NameError::TooLong => write!(f, "Must be at most {} characters", MAX_NAME_LENGTH),
+        }
     }
 }
 
-fn view_body(page_id: &browser::DomId, model: &Model) -> maud::Markup {
-    let filtered_people = model
-        .people
-        .iter()
-        .filter(|person| {
-            model.filter.is_empty() || person.last_name.to_lowercase().starts_with(&model.filter)
-        })
-        .collect::<Vec<&Person>>();
+// Returns the trimmed name if it is valid
+pub fn validate_name(name: &str) -> Result<String, NameError> {
+    let name = name.trim();
 
-    html! {
-        div id=(page_id) class="p-4" {
-            div {
-                label {
-                    div { "Filter" }
-                    input id=(Id::Filter) value=(model.filter) type="text";
-                }
-            }
+    if name.is_empty() {
+        Err(NameError::Required)
+    } else if name.chars().count() > MAX_NAME_LENGTH {
+        Err(NameError::TooLong)
+    } else {
+        Ok(name.to_string())
+    }
+}
 
-            select id=(Id::People) size="5" class="mt-4" {
-                @for person in filtered_people {
-                    option value=(person.id) selected[person.id == model.selected_id] {
-                        (format!("{}, {}", person.last_name, person.first_name))
-                    }
-                }
+pub struct Validation {
+    pub first_name: Result<String, NameError>,
+    pub last_name: Result<String, NameError>,
+}
+
+impl Validation {
+    pub fn is_valid(&self) -> bool {
+        self.first_name.is_ok() && self.last_name.is_ok()
+    }
+
+    pub fn to_person(&self, id: u32) -> Option<Person> {
+        match (&self.first_name, &self.last_name) {
+            (Ok(first_name), Ok(last_name)) => Some(Person {
+                id,
+                first_name: first_name.clone(),
+                last_name: last_name.clone(),
+            }),
+
+            _ => None,
+        }
+    }
+}
+
+fn view_head() -> maud::Markup {
+    layout::view_head(&registry::CRUD)
+}
+
+fn view_body(page_id: &browser::DomId, model: &Model) -> maud::Markup {
+    let filtered_people = model.filtered_people();
+    let selected_id = model.selected_person().map(|person| person.id);
+    let has_selection = selected_id.is_some();
+    let is_dirty = model.form.is_dirty();
+    let validation = model.form.validate();
+    let is_valid = validation.is_valid();
+    let (duplicate_on_create, duplicate_on_update) =
+        match (&validation.first_name, &validation.last_name) {
+            (Ok(first_name), Ok(last_name)) => (
+                model.is_duplicate(first_name, last_name, None),
+                has_selection && model.is_duplicate(first_name, last_name, selected_id),
+            ),
+            _ => (false, false),
+        };
+    let people_select = filtered_people.iter().fold(
+        Select::new(Id::People).size(5).class("mt-4 w-64"),
+        |select, person| {
+            select.option(
+                person.id,
+                format!("{}, {}", person.last_name, person.first_name),
+                person.id == model.selected_id,
+            )
+        },
+    );
+
+    let content = html! {
+        div class="p-4" {
+            (Field::new("Filter", Input::text(Id::Filter, &model.filter)))
+
+            (people_select)
+
+            (view_name_field("First name", Input::text(Id::FirstName, &model.form.first_name), &validation.first_name))
+            (view_name_field("Last name", Input::text(Id::LastName, &model.form.last_name), &validation.last_name))
+
+            @if duplicate_on_update {
+                div class="mt-2 text-sm text-amber-600" { "A person with this name already exists" }
+            } @else if duplicate_on_create {
+                div class="mt-2 text-sm text-amber-600" { "Create would add a second person with this name" }
             }
 
             div class="mt-4" {
-                label {
-                    div { "First name" }
-                    input id=(Id::FirstName) value=(model.form.first_name) type="text";
-                }
+                (Button::primary(Id::Create, "Create").class("w-20").disabled(!is_valid))
+                (Button::primary(Id::Update, "Update").class("w-20 ml-4").disabled(!has_selection || !is_valid))
+                (Button::primary(Id::Delete, "Delete").class("w-20 ml-4").disabled(!has_selection))
+                (Button::primary(Id::Discard, "Discard").class("w-20 ml-4").disabled(!is_dirty))
             }
 
-            div class="mt-4" {
-                label {
-                    div { "Last name" }
-                    input id=(Id::LastName) value=(model.form.last_name) type="text";
-                }
+            @if is_dirty {
+                div class="mt-2 text-sm text-amber-600" { "Unsaved changes" }
             }
 
+            (view_import_export(model))
+
             div class="mt-4" {
-                button id=(Id::Create) class="w-20 text-center items-center px-3 py-2 border border-transparent text-sm leading-4 font-medium rounded-md shadow-sm text-white bg-indigo-600 hover:bg-indigo-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-indigo-500" type="button" {
-                    "Create"
-                }
-                button id=(Id::Update) class="w-20 ml-4 text-center items-center px-3 py-2 border border-transparent text-sm leading-4 font-medium rounded-md shadow-sm text-white bg-indigo-600 hover:bg-indigo-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-indigo-500" type="button" {
-                    "Update"
-                }
-                button id=(Id::Delete) class="w-20 ml-4 text-center items-center px-3 py-2 border border-transparent text-sm leading-4 font-medium rounded-md shadow-sm text-white bg-indigo-600 hover:bg-indigo-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-indigo-500" type="button" {
-                    "Delete"
+                (Button::primary(Id::Undo, "Undo").class("w-20").disabled(!model.history.can_undo()))
+                (Button::primary(Id::Redo, "Redo").class("w-20 ml-4").disabled(!model.history.can_redo()))
+            }
+        }
+    };
+
+    layout::view_body(page_id, &registry::CRUD, model.error.as_ref(), content)
+}
+
+fn view_name_field(label: &str, input: Input, result: &Result<String, NameError>) -> maud::Markup {
+    let input = input.invalid(result.is_err());
+
+    html! {
+        (Field::new(label, input).error(result.as_ref().err()).class("mt-4"))
+    }
+}
+
+fn view_import_export(model: &Model) -> maud::Markup {
+    let is_replace = model.import_mode == ImportMode::Replace;
+    let import_mode_select = Select::new(Id::ImportMode)
+        .option(to_value(ImportMode::Merge), "Merge", !is_replace)
+        .option(to_value(ImportMode::Replace), "Replace", is_replace);
+
+    html! {
+        div class="mt-8" {
+            (Button::primary(Id::ExportCsv, "Export CSV").class("w-28"))
+            (Button::primary(Id::ExportJson, "Export JSON").class("w-28 ml-4"))
+        }
+
+        div class="mt-4" {
+            (Field::new("Import (.csv or .json)", Input::file(Id::ImportFile, ".csv,.json")))
+            (Field::new("Import mode", import_mode_select).class("mt-2"))
+        }
+
+        @if let Some(result) = &model.import_result {
+            div class="mt-2 text-sm" {
+                div { (format!("Imported {} people", result.imported)) }
+                @for err in &result.errors {
+                    div class="text-red-600" { (err) }
                 }
             }
         }
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+const SECOND: u64 = 1000;
+const MINUTE: u64 = 60 * SECOND;
+const HOUR: u64 = 60 * MINUTE;
+
+// Accepts units like 1m30s, 1.5h or 250ms, a bare number of seconds, or a clock like 01:30 or 00:01:30
+pub fn parse(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+
+    let millis = if s.contains(':') {
+        parse_clock(s)
+    } else {
+        parse_units(s)
+    };
+
+    millis
+        .map(Duration::from_millis)
+        .ok_or_else(|| format!("{} isn't a duration, try 1m30s, 90s or 00:01:30", s))
+}
+
+fn parse_clock(s: &str) -> Option<u64> {
+    let parts: Vec<&str> = s.split(':').collect();
+
+    let (hours, minutes, seconds) = match parts[..] {
+        [minutes, seconds] => ("0", minutes, seconds),
+        [hours, minutes, seconds] => (hours, minutes, seconds),
+        _ => return None,
+    };
+
+    let hours: u64 = parse_digits(hours)?;
+    let minutes: u64 = parse_digits(minutes)?;
+    let seconds = parse_number(seconds)?;
+
+    // Only the leading part may go past the next unit, as in 90:00
+    if (parts.len() == 3 && minutes >= 60) || seconds >= 60.0 {
+        return None;
+    }
+
+    hours
+        .checked_mul(HOUR)?
+        .checked_add(minutes.checked_mul(MINUTE)?)?
+        .checked_add(to_millis(seconds, SECOND)?)
+}
+
+fn parse_units(s: &str) -> Option<u64> {
+    if s.is_empty() {
+        return None;
+    }
+
+    if let Some(seconds) = parse_number(s) {
+        return to_millis(seconds, SECOND);
+    }
+
+    let mut millis: u64 = 0;
+    let mut rest = s;
+
+    while !rest.is_empty() {
+        let number_end = rest.find(|c: char| c.is_alphabetic())?;
+        let (number, after_number) = rest.split_at(number_end);
+
+        let unit_end = after_number
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(after_number.len());
+        let (unit, after_unit) = after_number.split_at(unit_end);
+
+        let unit_millis = match unit {
+            "h" => HOUR,
+            "m" => MINUTE,
+            "s" => SECOND,
+            "ms" => 1,
+            _ => return None,
+        };
+
+        millis = millis.checked_add(to_millis(parse_number(number.trim())?, unit_millis)?)?;
+        rest = after_unit.trim_start();
+    }
+
+    Some(millis)
+}
+
+fn parse_digits(s: &str) -> Option<u64> {
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    s.parse().ok()
+}
+
+fn parse_number(s: &str) -> Option<f64> {
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+
+    s.parse().ok()
+}
+
+// None when the result doesn't fit, a long enough string of digits even parses as infinity
+fn to_millis(amount: f64, unit_millis: u64) -> Option<u64> {
+    let millis = (amount * unit_millis as f64).round();
+
+    if millis.is_finite() && millis < u64::MAX as f64 {
+        Some(millis as u64)
+    } else {
+        None
+    }
+}
+
+// The shortest text that parses back to the same duration, such as 1m30s
+pub fn format_input(duration: Duration) -> String {
+    let millis = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+    let hours = millis / HOUR;
+    let minutes = millis % HOUR / MINUTE;
+    let seconds = (millis % MINUTE) as f64 / SECOND as f64;
+
+    let mut parts = vec![];
+
+    if hours > 0 {
+        parts.push(format!("{}h", hours));
+    }
+
+    if minutes > 0 {
+        parts.push(format!("{}m", minutes));
+    }
+
+    if seconds > 0.0 || parts.is_empty() {
+        parts.push(format!("{}s", seconds));
+    }
+
+    parts.concat()
+}
+
+// Seconds with hundredths under a minute, then mm:ss and hh:mm:ss
+pub fn format_display(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+
+    if seconds < 60 {
+        format!("{:.2}s", duration.as_secs_f64())
+    } else if seconds < 60 * 60 {
+        format!("{:02}:{:02}", seconds / 60, seconds % 60)
+    } else {
+        format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60
+        )
+    }
+}
@@ -1,3 +1,15 @@
+use crate::clock::Clock;
+use crate::components::Button;
+use crate::components::Field;
+use crate::components::Input;
+use crate::components::Select;
+use crate::hydration::Validate;
+use crate::layout;
+use crate::page_error;
+use crate::page_error::ErrorState;
+use crate::page_error::PageError;
+use crate::registry;
+use crate::runtime;
 use chrono::NaiveDate;
 use maud::html;
 use polyester::browser;
@@ -8,32 +20,54 @@ use polyester::browser::ToDomId;
 use polyester::browser::Value;
 use polyester::page::Page;
 use polyester::page::PageMarkup;
-use polyester::time;
 use polyester::time::Posix;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::rc::Rc;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub enum Model {
+pub struct Model {
+    pub state: State,
+    pub error: Option<PageError>,
+}
+
+impl Validate for Model {}
+
+impl ErrorState for Model {
+    fn error(&self) -> Option<&PageError> {
+        self.error.as_ref()
+    }
+
+    fn set_error(&mut self, error: Option<PageError>) {
+        self.error = error;
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum State {
     Drafting(Draft),
     Booked(Flight),
 }
 
 pub struct FlightPage {
-    pub initial_time: time::Posix,
+    pub clock: Rc<dyn Clock>,
 }
 
 impl FlightPage {
     fn initial_model(&self) -> Model {
-        let now = posix_to_naive_date_time(&self.initial_time);
-
-        Model::Drafting(Draft {
-            flight_type: FlightType::OneWay,
-            current_date: now.date(),
-            start_date: Date::Valid(now.date()),
-            return_date: Date::Valid(now.date()),
-        })
+        let now = posix_to_naive_date_time(&self.clock.now());
+
+        Model {
+            state: State::Drafting(Draft {
+                flight_type: FlightType::OneWay,
+                current_date: now.date(),
+                start_date: Date::Valid(now.date()),
+                return_date: Date::Valid(now.date()),
+            }),
+            error: None,
+        }
     }
 }
 
@@ -50,35 +84,61 @@ impl Page<Model, Msg, AppEffect> for FlightPage {
     }
 
     fn subscriptions(&self, model: &Model) -> browser::Subscriptions<Msg, AppEffect> {
-        match model {
-            Model::Drafting(_) => {
+        match model.state {
+            State::Drafting(_) => {
                 vec![
                     browser::on_change(&Id::FlightType, Msg::FlightTypeChanged),
                     browser::on_change_string(&Id::StartDate, Msg::StartDateChanged),
                     browser::on_change_string(&Id::ReturnDate, Msg::ReturnDateChanged),
                     browser::on_click(&Id::Book, Msg::BookFlight),
+                    browser::on_click(&page_error::Id::DismissError, Msg::ErrorDismissed),
                 ]
             }
 
-            Model::Booked(_) => {
-                vec![browser::on_click(&Id::Reset, Msg::Reset)]
+            State::Booked(_) => {
+                vec![
+                    browser::on_click(&Id::Reset, Msg::Reset),
+                    browser::on_click(&page_error::Id::DismissError, Msg::ErrorDismissed),
+                ]
             }
         }
     }
 
-    fn update(&self, msg: &Msg, mut model: &mut Model) -> Result<Effects<Msg, AppEffect>, String> {
-        match (&mut model, msg) {
-            (Model::Drafting(draft), Msg::FlightTypeChanged(value)) => {
+    fn update(&self, msg: &Msg, model: &mut Model) -> Result<Effects<Msg, AppEffect>, String> {
+        runtime::update(model, |model| self.try_update(msg, model))
+    }
+
+    fn view(&self, model: &Model) -> PageMarkup {
+        PageMarkup {
+            head: view_head(),
+            body: view_body(&self.id(), model),
+        }
+    }
+}
+
+impl FlightPage {
+    fn try_update(
+        &self,
+        msg: &Msg,
+        model: &mut Model,
+    ) -> Result<Effects<Msg, AppEffect>, PageError> {
+        match (&mut model.state, msg) {
+            (_, Msg::ErrorDismissed) => {
+                model.error = None;
+                Ok(vec![])
+            }
+
+            (State::Drafting(draft), Msg::FlightTypeChanged(value)) => {
                 let flight_type = value
                     .parse()
-                    .map_err(|err| format!("Failed to parse flight type: {}", err))?;
+                    .map_err(|err| PageError::parse("flight type", err))?;
 
                 draft.flight_type = flight_type;
 
                 Ok(vec![])
             }
 
-            (Model::Drafting(draft), Msg::StartDateChanged(value)) => {
+            (State::Drafting(draft), Msg::StartDateChanged(value)) => {
                 draft.start_date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
                     .map(Date::Valid)
                     .unwrap_or_else(|_| Date::Invalid(value.to_string()));
@@ -86,7 +146,7 @@ impl Page<Model, Msg, AppEffect> for FlightPage {
                 Ok(vec![])
             }
 
-            (Model::Drafting(draft), Msg::ReturnDateChanged(value)) => {
+            (State::Drafting(draft), Msg::ReturnDateChanged(value)) => {
                 draft.return_date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
                     .map(Date::Valid)
                     .unwrap_or_else(|_| Date::Invalid(value.to_string()));
@@ -94,9 +154,10 @@ impl Page<Model, Msg, AppEffect> for FlightPage {
                 Ok(vec![])
             }
 
-            (Model::Drafting(draft), Msg::BookFlight) => {
-                let flight = Flight::from_draft(draft).ok_or("Failed to book flight")?;
-                *model = Model::Booked(flight);
+            (State::Drafting(draft), Msg::BookFlight) => {
+                let flight = Flight::from_draft(draft)
+                    .ok_or_else(|| PageError::invalid_state("book", "Failed to book flight"))?;
+                model.state = State::Booked(flight);
 
                 Ok(vec![])
             }
@@ -108,21 +169,14 @@ impl Page<Model, Msg, AppEffect> for FlightPage {
                 Ok(vec![])
             }
 
-            (Model::Booked(_), _) => Ok(vec![]),
-        }
-    }
-
-    fn view(&self, model: &Model) -> PageMarkup {
-        PageMarkup {
-            head: view_head(),
-            body: view_body(&self.id(), model),
+            (State::Booked(_), _) => Ok(vec![]),
         }
     }
 }
 
 #[derive(strum_macros::Display, polyester_macro::ToDomId)]
 #[strum(serialize_all = "kebab-case")]
-enum Id {
+pub enum Id {
     FlightType,
     StartDate,
     ReturnDate,
@@ -138,6 +192,7 @@ pub enum Msg {
     ReturnDateChanged(String),
     BookFlight,
     Reset,
+    ErrorDismissed,
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
@@ -145,27 +200,25 @@ pub enum Msg {
 pub enum AppEffect {}
 
 fn view_head() -> maud::Markup {
-    html! {
-        title { "Flight booker" }
-        link rel="stylesheet" href="/app.css";
-        script defer type="module" src="/flight_page.js" {}
-    }
+    layout::view_head(&registry::FLIGHT)
 }
 
 fn view_body(page_id: &DomId, model: &Model) -> maud::Markup {
-    html! {
-        div id=(page_id) class="p-4" {
-            @match model {
-                Model::Drafting(draft) => {
+    let content = html! {
+        div class="p-4" {
+            @match &model.state {
+                State::Drafting(draft) => {
                     (view_form(draft))
                 },
 
-                Model::Booked(flight) => {
+                State::Booked(flight) => {
                     (view_success(flight))
                 },
             }
         }
-    }
+    };
+
+    layout::view_body(page_id, &registry::FLIGHT, model.error.as_ref(), content)
 }
 
 fn view_form(draft: &Draft) -> maud::Markup {
@@ -173,40 +226,27 @@ fn view_form(draft: &Draft) -> maud::Markup {
     let start_date_is_ok = draft.start_date_is_ok(&draft.current_date);
     let return_date_is_ok = draft.return_date_is_ok(&draft.current_date);
     let can_book = start_date_is_ok && return_date_is_ok;
+    let flight_type_select = Select::new(Id::FlightType)
+        .option(
+            to_value(FlightType::OneWay),
+            "One-way flight",
+            flight_type.is_one_way(),
+        )
+        .option(
+            to_value(FlightType::Return),
+            "Return flight",
+            !flight_type.is_one_way(),
+        );
 
     html! {
-        div {
-            label for=(Id::FlightType) { "Flight type" }
-            div {
-                select id=(Id::FlightType) {
-                    option value=(to_value(FlightType::OneWay)) selected[flight_type.is_one_way()] {
-                        "One-way flight"
-                    }
-                    option value=(to_value(FlightType::Return)) selected[!flight_type.is_one_way()] {
-                        "Return flight"
-                    }
-                }
-            }
-        }
+        (Field::new("Flight type", flight_type_select))
 
-        div class="mt-4"{
-            label for=(Id::StartDate) { "Start date" }
-            div {
-                input id=(Id::StartDate) type="date" style=(conditional(!start_date_is_ok, "background: coral;")) value=(draft.start_date);
-            }
-        }
+        (Field::new("Start date", Input::date(Id::StartDate, &draft.start_date).invalid(!start_date_is_ok)).class("mt-4"))
 
-        div class="mt-4" {
-            label for=(Id::ReturnDate) { "End date" }
-            div {
-                input id=(Id::ReturnDate) type="date" style=(conditional(!return_date_is_ok, "background: coral;")) value=(draft.return_date) disabled[!draft.return_date_is_needed()];
-            }
-        }
+        (Field::new("End date", Input::date(Id::ReturnDate, &draft.return_date).invalid(!return_date_is_ok).disabled(!draft.return_date_is_needed())).class("mt-4"))
 
         div class="mt-4"{
-            button id=(Id::Book) disabled[!can_book] class="text-center items-center px-3 py-2 border border-transparent text-sm leading-4 font-medium rounded-md shadow-sm text-white bg-indigo-600 hover:bg-indigo-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-indigo-500" type="button" {
-                "Book"
-            }
+            (Button::primary(Id::Book, "Book").disabled(!can_book))
         }
     }
 }
@@ -235,9 +275,7 @@ fn view_success(flight: &Flight) -> maud::Markup {
 
     html! {
         div { (message) }
-        button id=(Id::Reset) class="mt-4 text-center items-center px-3 py-2 border border-transparent text-sm leading-4 font-medium rounded-md shadow-sm text-white bg-indigo-600 hover:bg-indigo-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-indigo-500" type="button" {
-            "Book another flight"
-        }
+        (Button::primary(Id::Reset, "Book another flight").class("mt-4"))
 
     }
 }
@@ -348,20 +386,6 @@ impl FlightType {
     }
 }
 
-fn conditional<T>(should_render: bool, value: T) -> Optional<T> {
-    Optional(should_render.then(|| value))
-}
-
-struct Optional<T>(Option<T>);
-
-impl<T: maud::Render> maud::Render for Optional<T> {
-    fn render_to(&self, buffer: &mut String) {
-        if let Some(ref value) = self.0 {
-            value.render_to(buffer);
-        }
-    }
-}
-
 fn posix_to_naive_date_time(posix: &Posix) -> chrono::NaiveDateTime {
     let ns = (posix.as_millis() % 1000) * 1_000_000;
     chrono::NaiveDateTime::from_timestamp(posix.as_secs() as i64, ns.try_into().unwrap_or(0))
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+pub const DEFAULT_LIMIT: usize = 100;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", from = "StoredHistory<T>")]
+pub struct History<T> {
+    undo_stack: VecDeque<T>,
+    redo_stack: Vec<T>,
+    limit: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredHistory<T> {
+    undo_stack: VecDeque<T>,
+    redo_stack: Vec<T>,
+    limit: usize,
+}
+
+// A stored history may hold more than its limit, only the newest undo and nearest redo entries are kept
+impl<T> From<StoredHistory<T>> for History<T> {
+    fn from(stored: StoredHistory<T>) -> History<T> {
+        let mut history = History {
+            undo_stack: stored.undo_stack,
+            redo_stack: stored.redo_stack,
+            limit: stored.limit,
+        };
+
+        history.trim_undo();
+        let excess = history.redo_stack.len().saturating_sub(history.limit);
+        history.redo_stack.drain(..excess);
+
+        history
+    }
+}
+
+impl<T> History<T> {
+    pub fn new(limit: usize) -> History<T> {
+        History {
+            undo_stack: VecDeque::new(),
+            redo_stack: vec![],
+            limit,
+        }
+    }
+
+    // Records the state before a change, dropping the oldest entry when full
+    pub fn record(&mut self, snapshot: T) {
+        if self.limit == 0 {
+            return;
+        }
+
+        while self.undo_stack.len() >= self.limit {
+            self.undo_stack.pop_front();
+        }
+
+        self.undo_stack.push_back(snapshot);
+        self.redo_stack.clear();
+    }
+
+    fn trim_undo(&mut self) {
+        while self.undo_stack.len() > self.limit {
+            self.undo_stack.pop_front();
+        }
+    }
+
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let previous = self.undo_stack.pop_back()?;
+        self.redo_stack.push(current);
+        Some(previous)
+    }
+
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let next = self.redo_stack.pop()?;
+        self.undo_stack.push_back(current);
+        self.trim_undo();
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+}
+
+impl<T> Default for History<T> {
+    fn default() -> History<T> {
+        History::new(DEFAULT_LIMIT)
+    }
+}
+
+pub trait Undoable {
+    type Snapshot;
+
+    fn history(&mut self) -> &mut History<Self::Snapshot>;
+
+    fn snapshot(&self) -> Self::Snapshot;
+
+    fn restore(&mut self, snapshot: Self::Snapshot);
+
+    fn record(&mut self) {
+        let snapshot = self.snapshot();
+        self.history().record(snapshot);
+    }
+
+    fn undo(&mut self) {
+        let current = self.snapshot();
+
+        if let Some(previous) = self.history().undo(current) {
+            self.restore(previous);
+        }
+    }
+
+    fn redo(&mut self) {
+        let current = self.snapshot();
+
+        if let Some(next) = self.history().redo(current) {
+            self.restore(next);
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use crate::hydration::Validate;
+use crate::layout;
+use crate::registry;
 use maud::html;
 use polyester::browser;
 use polyester::browser::DomId;
@@ -10,6 +13,8 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub struct Model {}
 
+impl Validate for Model {}
+
 pub struct HomePage {}
 
 impl Page<Model, Msg, AppEffect> for HomePage {
@@ -50,48 +55,26 @@ pub enum Msg {}
 pub enum AppEffect {}
 
 fn view_head() -> maud::Markup {
-    html! {
-        title { "Home page" }
-        link rel="stylesheet" href="/app.css";
-    }
+    layout::view_head(&registry::HOME)
 }
 
 fn view_body(page_id: &browser::DomId, _model: &Model) -> maud::Markup {
-    html! {
-        div id=(page_id) class="p-4" {
+    let content = html! {
+        div class="p-4" {
             h1 class="text-xl text-bold" { "7GUIs demos" }
 
             ul class="mt-4" {
-                li {
-                    a class="underline text-blue-600 hover:text-blue-800 visited:text-purple-600" href="/counter/" {
-                        "Counter"
-                    }
-                }
-
-                li {
-                    a class="underline text-blue-600 hover:text-blue-800 visited:text-purple-600" href="/temperature/" {
-                        "Temperature Converter"
-                    }
-                }
-
-                li {
-                    a class="underline text-blue-600 hover:text-blue-800 visited:text-purple-600" href="/flight/" {
-                        "Flight Booker"
-                    }
-                }
-
-                li {
-                    a class="underline text-blue-600 hover:text-blue-800 visited:text-purple-600" href="/timer/" {
-                        "Timer"
-                    }
-                }
-
-                li {
-                    a class="underline text-blue-600 hover:text-blue-800 visited:text-purple-600" href="/crud/" {
-                        "CRUD"
+                @for page in registry::PAGES.iter().filter(|page| page.route != registry::HOME.route) {
+                    li {
+                        a class="underline text-blue-600 hover:text-blue-800 visited:text-purple-600" href=(page.route) {
+                            (page.title)
+                        }
+                        span class="ml-2 text-sm text-gray-600" { (page.description) }
                     }
                 }
             }
         }
-    }
+    };
+
+    layout::view_body(page_id, &registry::HOME, None, content)
 }
@@ -0,0 +1,113 @@
+use maud::html;
+use maud::PreEscaped;
+use polyester::browser;
+use polyester::browser::DomId;
+use polyester::browser::Effects;
+use polyester::page::Page;
+use polyester::page::PageMarkup;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashSet;
+
+// Id of the script element that carries the serialized model
+pub const MODEL_ID: &str = "sevenguis-model";
+
+// Adds the serialized model to the head so the browser can pick up where the server left off
+pub fn embed_model<Model: Serialize>(
+    markup: PageMarkup,
+    model: &Model,
+) -> Result<PageMarkup, String> {
+    let json =
+        serde_json::to_string(model).map_err(|err| format!("Failed to encode model: {}", err))?;
+
+    // Keeps strings like "</script>" in the model from ending the element early
+    let json = json.replace('<', "\\u003c");
+
+    Ok(PageMarkup {
+        head: html! {
+            (markup.head)
+            script id=(MODEL_ID) type="application/json" { (PreEscaped(json)) }
+        },
+        body: markup.body,
+    })
+}
+
+// Checks what decoding alone can't, so an embedded model holds up like one the page built itself
+pub trait Validate {
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+// Ids must be unique and below the next id, the way pages hand them out
+pub fn validate_ids(ids: impl Iterator<Item = u32>, next_id: u32) -> Result<(), String> {
+    let mut seen = HashSet::new();
+
+    for id in ids {
+        if !seen.insert(id) {
+            return Err(format!("Duplicate id: {}", id));
+        }
+
+        if id >= next_id {
+            return Err(format!("Id {} isn't below the next id {}", id, next_id));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn decode_model<Model: DeserializeOwned + Validate>(json: &str) -> Result<Model, String> {
+    let model: Model =
+        serde_json::from_str(json).map_err(|err| format!("Failed to decode model: {}", err))?;
+
+    model
+        .validate()
+        .map_err(|err| format!("Invalid model: {}", err))?;
+
+    Ok(model)
+}
+
+// Starts a page from an embedded model instead of the one its init builds
+pub struct Hydrated<P> {
+    pub page: P,
+    pub model: Option<String>,
+}
+
+impl<P> Hydrated<P> {
+    pub fn new(page: P, model: Option<String>) -> Hydrated<P> {
+        Hydrated { page, model }
+    }
+}
+
+impl<P, Model, Msg, AppEffect> Page<Model, Msg, AppEffect> for Hydrated<P>
+where
+    P: Page<Model, Msg, AppEffect>,
+    Model: DeserializeOwned + Validate,
+{
+    fn id(&self) -> DomId {
+        self.page.id()
+    }
+
+    // Init's effects still run, so data stored in the browser is loaded over the embedded model.
+    // A model that can't be decoded falls back to init rather than leaving the page blank.
+    fn init(&self) -> (Model, Effects<Msg, AppEffect>) {
+        let (init_model, effects) = self.page.init();
+
+        match self.model.as_deref().map(decode_model) {
+            Some(Ok(model)) => (model, effects),
+            _ => (init_model, effects),
+        }
+    }
+
+    fn subscriptions(&self, model: &Model) -> browser::Subscriptions<Msg, AppEffect> {
+        self.page.subscriptions(model)
+    }
+
+    fn update(&self, msg: &Msg, model: &mut Model) -> Result<Effects<Msg, AppEffect>, String> {
+        self.page.update(msg, model)
+    }
+
+    fn view(&self, model: &Model) -> PageMarkup {
+        self.page.view(model)
+    }
+}
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Split {
+    pub number: usize,
+    // Time since the previous lap, or since the start for the first one
+    pub delta: Duration,
+    pub total: Duration,
+}
+
+// Laps are stored as the elapsed time when they were recorded
+pub fn splits(laps: &[Duration]) -> Vec<Split> {
+    let previous = std::iter::once(Duration::ZERO).chain(laps.iter().copied());
+
+    laps.iter()
+        .zip(previous)
+        .enumerate()
+        .map(|(index, (total, previous))| Split {
+            number: index + 1,
+            delta: total.saturating_sub(previous),
+            total: *total,
+        })
+        .collect()
+}
+
+const CSV_HEADER: [&str; 3] = ["lap", "delta_seconds", "total_seconds"];
+
+pub fn encode_csv(laps: &[Duration]) -> String {
+    let mut lines = vec![CSV_HEADER.join(",")];
+
+    for split in splits(laps) {
+        let fields = [
+            split.number.to_string(),
+            format!("{:.3}", split.delta.as_secs_f64()),
+            format!("{:.3}", split.total.as_secs_f64()),
+        ];
+
+        lines.push(fields.join(","));
+    }
+
+    lines.join("\n") + "\n"
+}
@@ -0,0 +1,84 @@
+use crate::page_error;
+use crate::page_error::PageError;
+use crate::registry;
+use crate::registry::PageInfo;
+use maud::html;
+use polyester::browser::DomId;
+
+pub fn page_title(page: &PageInfo) -> String {
+    if page.route == registry::HOME.route {
+        registry::HOME.title.to_string()
+    } else {
+        format!("{} · {}", page.title, registry::HOME.title)
+    }
+}
+
+pub fn view_head(page: &PageInfo) -> maud::Markup {
+    html! {
+        meta charset="utf-8";
+        meta name="viewport" content="width=device-width, initial-scale=1";
+        meta name="description" content=(page.description);
+        title { (page_title(page)) }
+        link rel="stylesheet" href="/app.css";
+        @if let Some(script) = page.script {
+            script defer type="module" src=(script) {}
+        }
+    }
+}
+
+// The layout lives inside the page element, since that is the element polyester re-renders
+pub fn view_body(
+    page_id: &DomId,
+    page: &PageInfo,
+    error: Option<&PageError>,
+    content: maud::Markup,
+) -> maud::Markup {
+    html! {
+        div id=(page_id) class="min-h-screen flex flex-col" {
+            (view_nav(page))
+            (page_error::view_banner(error))
+
+            main class="flex-1" {
+                (content)
+            }
+
+            (view_footer())
+        }
+    }
+}
+
+fn view_nav(current: &PageInfo) -> maud::Markup {
+    html! {
+        header class="border-b border-gray-200 bg-gray-50" {
+            nav class="flex flex-wrap items-center gap-x-4 gap-y-2 px-4 py-3 text-sm" {
+                a href=(registry::HOME.route) class="font-semibold text-gray-900 mr-2" {
+                    (registry::HOME.title)
+                }
+
+                @for page in registry::PAGES.iter().filter(|page| page.route != registry::HOME.route) {
+                    @if page.route == current.route {
+                        a href=(page.route) aria-current="page" class="font-medium text-indigo-600 border-b-2 border-indigo-600" {
+                            (page.title)
+                        }
+                    } @else {
+                        a href=(page.route) class="text-gray-600 hover:text-gray-900" {
+                            (page.title)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn view_footer() -> maud::Markup {
+    html! {
+        footer class="border-t border-gray-200 px-4 py-3 text-xs text-gray-500" {
+            "Implementations of the "
+            a href="https://eugenkiss.github.io/7guis/" class="underline hover:text-gray-700" { "7GUIs" }
+            " tasks, built with "
+            a href="https://github.com/polyester-web/polyester-rs" class="underline hover:text-gray-700" { "polyester" }
+            "."
+        }
+    }
+}
@@ -1,6 +1,20 @@
+pub mod cells_page;
+pub mod circle_page;
+pub mod clock;
+pub mod components;
 pub mod counter_page;
+pub mod crud_codec;
 pub mod crud_page;
+pub mod duration;
 pub mod flight_page;
+pub mod history;
 pub mod home_page;
+pub mod hydration;
+pub mod laps;
+pub mod layout;
+pub mod page_error;
+pub mod registry;
+pub mod runtime;
+pub mod spreadsheet;
 pub mod temperature_page;
 pub mod timer_page;
@@ -0,0 +1,90 @@
+use maud::html;
+use polyester::browser::ToDomId;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(strum_macros::Display, polyester_macro::ToDomId)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Id {
+    PageError,
+    DismissError,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorKind {
+    // A value from the browser couldn't be parsed
+    InvalidInput,
+    // The message doesn't apply to the model as it is
+    InvalidState,
+}
+
+impl ErrorKind {
+    pub fn title(&self) -> &'static str {
+        match self {
+            ErrorKind::InvalidInput => "Invalid input",
+            ErrorKind::InvalidState => "Not possible right now",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageError {
+    pub kind: ErrorKind,
+    pub message: String,
+    // What was being parsed or attempted, such as "max duration" or "delete"
+    pub context: String,
+}
+
+impl PageError {
+    pub fn parse(context: &str, err: impl fmt::Display) -> PageError {
+        PageError {
+            kind: ErrorKind::InvalidInput,
+            message: err.to_string(),
+            context: context.to_string(),
+        }
+    }
+
+    pub fn invalid_state(context: &str, message: &str) -> PageError {
+        PageError {
+            kind: ErrorKind::InvalidState,
+            message: message.to_string(),
+            context: context.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for PageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ErrorKind::InvalidInput => {
+                write!(f, "Failed to parse {}: {}", self.context, self.message)
+            }
+
+            ErrorKind::InvalidState => write!(f, "{}", self.message),
+        }
+    }
+}
+
+// Models keep their most recent error, so the view can show it until it's dismissed
+pub trait ErrorState {
+    fn error(&self) -> Option<&PageError>;
+    fn set_error(&mut self, error: Option<PageError>);
+}
+
+pub fn view_banner(error: Option<&PageError>) -> maud::Markup {
+    html! {
+        @if let Some(error) = error {
+            div id=(Id::PageError) role="alert" class="mx-4 mt-4 flex items-start justify-between rounded-md border border-red-300 bg-red-50 p-3 text-sm text-red-800" {
+                div {
+                    span class="font-medium" { (error.kind.title()) ": " }
+                    (error)
+                }
+                button id=(Id::DismissError) type="button" aria-label="Dismiss" class="ml-4 font-medium text-red-600 hover:text-red-800" {
+                    "×"
+                }
+            }
+        }
+    }
+}
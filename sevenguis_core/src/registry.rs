@@ -0,0 +1,163 @@
+use crate::cells_page;
+use crate::circle_page;
+use crate::clock::Clock;
+use crate::counter_page;
+use crate::crud_page;
+use crate::flight_page;
+use crate::home_page;
+use crate::hydration;
+use crate::temperature_page;
+use crate::timer_page;
+use polyester::page::Page;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+pub struct PageInfo {
+    pub slug: &'static str,
+    pub route: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    pub script: Option<&'static str>,
+    // Time dependent pages read the clock, the others ignore it
+    pub new: fn(Rc<dyn Clock>) -> Box<dyn RenderPage>,
+}
+
+pub const HOME: PageInfo = PageInfo {
+    slug: "home",
+    route: "/",
+    title: "7GUIs",
+    description: "Implementations of the 7GUIs tasks.",
+    script: None,
+    new: |_| boxed(home_page::HomePage {}),
+};
+
+pub const COUNTER: PageInfo = PageInfo {
+    slug: "counter",
+    route: "/counter/",
+    title: "Counter",
+    description: "Count clicks, with undo and redo.",
+    script: Some("/counter_page.js"),
+    new: |_| boxed(counter_page::CounterPage {}),
+};
+
+pub const TEMPERATURE: PageInfo = PageInfo {
+    slug: "temperature",
+    route: "/temperature/",
+    title: "Temperature Converter",
+    description: "Convert between Celsius and Fahrenheit in both directions.",
+    script: Some("/temperature_page.js"),
+    new: |_| boxed(temperature_page::TemperaturePage {}),
+};
+
+pub const FLIGHT: PageInfo = PageInfo {
+    slug: "flight",
+    route: "/flight/",
+    title: "Flight Booker",
+    description: "Book a one-way or return flight, rejecting impossible dates.",
+    script: Some("/flight_page.js"),
+    new: |clock| boxed(flight_page::FlightPage { clock }),
+};
+
+pub const TIMER: PageInfo = PageInfo {
+    slug: "timer",
+    route: "/timer/",
+    title: "Timer",
+    description: "Run named timers side by side, each filling up its own duration.",
+    script: Some("/timer_page.js"),
+    new: |clock| boxed(timer_page::TimerPage { clock }),
+};
+
+pub const CRUD: PageInfo = PageInfo {
+    slug: "crud",
+    route: "/crud/",
+    title: "CRUD",
+    description: "Create, filter, update and delete people.",
+    script: Some("/crud_page.js"),
+    new: |_| boxed(crud_page::CrudPage {}),
+};
+
+pub const CIRCLE: PageInfo = PageInfo {
+    slug: "circle",
+    route: "/circle/",
+    title: "Circle Drawer",
+    description: "Draw circles and adjust their diameter, with undo and redo.",
+    script: Some("/circle_page.js"),
+    new: |_| boxed(circle_page::CirclePage {}),
+};
+
+pub const CELLS: PageInfo = PageInfo {
+    slug: "cells",
+    route: "/cells/",
+    title: "Cells",
+    description: "A spreadsheet whose formulas update as the cells they use change.",
+    script: Some("/cells_page.js"),
+    new: |_| boxed(cells_page::CellsPage {}),
+};
+
+// In the order the 7GUIs tasks are listed
+pub const PAGES: [&PageInfo; 8] = [
+    &HOME,
+    &COUNTER,
+    &TEMPERATURE,
+    &FLIGHT,
+    &TIMER,
+    &CRUD,
+    &CIRCLE,
+    &CELLS,
+];
+
+pub fn find(slug: &str) -> Option<&'static PageInfo> {
+    PAGES.into_iter().find(|page| page.slug == slug)
+}
+
+pub fn find_by_route(route: &str) -> Option<&'static PageInfo> {
+    PAGES.into_iter().find(|page| page.route == route)
+}
+
+// A page with its types erased, so pages of different types can share a list
+pub trait RenderPage {
+    // Renders the initial view, or the view of the given model with the model embedded for hydration
+    fn render(&self, model_json: Option<&str>) -> Result<String, String>;
+}
+
+struct Renderer<P, Model, Msg, AppEffect> {
+    page: P,
+    types: PhantomData<(Model, Msg, AppEffect)>,
+}
+
+impl<P, Model, Msg, AppEffect> RenderPage for Renderer<P, Model, Msg, AppEffect>
+where
+    P: Page<Model, Msg, AppEffect>,
+    Model: Serialize + DeserializeOwned + hydration::Validate,
+{
+    fn render(&self, model_json: Option<&str>) -> Result<String, String> {
+        let markup = match model_json {
+            Some(json) => {
+                let model: Model = hydration::decode_model(json)?;
+                hydration::embed_model(self.page.view(&model), &model)?
+            }
+
+            None => {
+                let (model, _effects) = self.page.init();
+                self.page.view(&model)
+            }
+        };
+
+        Ok(markup.to_markup().into_string())
+    }
+}
+
+fn boxed<P, Model, Msg, AppEffect>(page: P) -> Box<dyn RenderPage>
+where
+    P: Page<Model, Msg, AppEffect> + 'static,
+    Model: Serialize + DeserializeOwned + hydration::Validate + 'static,
+    Msg: 'static,
+    AppEffect: 'static,
+{
+    Box::new(Renderer {
+        page,
+        types: PhantomData,
+    })
+}
@@ -0,0 +1,26 @@
+use crate::page_error::ErrorState;
+use crate::page_error::PageError;
+use polyester::browser::Effects;
+
+// Applies a message as a transaction: a failed update leaves the model as it was before the message,
+// apart from the error it reports. Errors are kept in the model instead of being returned, since the
+// browser runtime doesn't render a failed update.
+pub fn update<Model, Msg, AppEffect>(
+    model: &mut Model,
+    update: impl FnOnce(&mut Model) -> Result<Effects<Msg, AppEffect>, PageError>,
+) -> Result<Effects<Msg, AppEffect>, String>
+where
+    Model: Clone + ErrorState,
+{
+    let before = model.clone();
+
+    match update(model) {
+        Ok(effects) => Ok(effects),
+
+        Err(err) => {
+            *model = before;
+            model.set_error(Some(err));
+            Ok(vec![])
+        }
+    }
+}
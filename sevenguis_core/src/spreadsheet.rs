@@ -0,0 +1,753 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+
+pub const COLUMNS: u8 = 26;
+pub const ROWS: u8 = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct CellId {
+    pub column: u8,
+    pub row: u8,
+}
+
+impl CellId {
+    pub fn new(column: u8, row: u8) -> Option<CellId> {
+        if column < COLUMNS && row < ROWS {
+            Some(CellId { column, row })
+        } else {
+            None
+        }
+    }
+
+    pub fn column_name(column: u8) -> char {
+        (b'A' + column) as char
+    }
+
+    pub fn parse(s: &str) -> Option<CellId> {
+        let mut chars = s.chars();
+        let letter = chars.next()?.to_ascii_uppercase();
+        if !letter.is_ascii_uppercase() {
+            return None;
+        }
+
+        let digits = chars.as_str();
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let row = digits.parse().ok()?;
+        CellId::new(letter as u8 - b'A', row)
+    }
+}
+
+impl fmt::Display for CellId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", CellId::column_name(self.column), self.row)
+    }
+}
+
+impl From<CellId> for String {
+    fn from(id: CellId) -> String {
+        id.to_string()
+    }
+}
+
+impl TryFrom<String> for CellId {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<CellId, String> {
+        CellId::parse(&s).ok_or_else(|| format!("Invalid cell id: {}", s))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CellValue {
+    Empty,
+    Number(f64),
+    Text(String),
+    Error(CellError),
+}
+
+impl fmt::Display for CellValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CellValue::Empty => Ok(()),
+            CellValue::Number(n) => write!(f, "{}", n),
+            CellValue::Text(s) => write!(f, "{}", s),
+            CellValue::Error(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CellError {
+    Parse,
+    Cycle,
+    DivisionByZero,
+    Value,
+}
+
+impl fmt::Display for CellError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CellError::Parse => write!(f, "#PARSE!"),
+            CellError::Cycle => write!(f, "#CYCLE!"),
+            CellError::DivisionByZero => write!(f, "#DIV/0!"),
+            CellError::Value => write!(f, "#VALUE!"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Formula {
+    Empty,
+    Number(f64),
+    Text(String),
+    Ref(CellId),
+    Range(CellId, CellId),
+    Negate(Box<Formula>),
+    Binary(Operator, Box<Formula>, Box<Formula>),
+    Call(Function, Vec<Formula>),
+    Invalid(String),
+}
+
+impl Formula {
+    pub fn parse(content: &str) -> Formula {
+        let trimmed = content.trim();
+
+        if trimmed.is_empty() {
+            Formula::Empty
+        } else if let Some(expr) = trimmed.strip_prefix('=') {
+            Parser::parse(expr).unwrap_or_else(Formula::Invalid)
+        } else if let Ok(n) = trimmed.parse::<f64>() {
+            if n.is_finite() {
+                Formula::Number(n)
+            } else {
+                Formula::Text(content.to_string())
+            }
+        } else {
+            Formula::Text(content.to_string())
+        }
+    }
+
+    pub fn references(&self) -> BTreeSet<CellId> {
+        let mut refs = BTreeSet::new();
+        self.collect_references(&mut refs);
+        refs
+    }
+
+    fn collect_references(&self, refs: &mut BTreeSet<CellId>) {
+        match self {
+            Formula::Ref(id) => {
+                refs.insert(*id);
+            }
+
+            Formula::Range(from, to) => {
+                refs.extend(range_cells(from, to));
+            }
+
+            Formula::Negate(formula) => {
+                formula.collect_references(refs);
+            }
+
+            Formula::Binary(_, left, right) => {
+                left.collect_references(refs);
+                right.collect_references(refs);
+            }
+
+            Formula::Call(_, args) => {
+                for arg in args {
+                    arg.collect_references(refs);
+                }
+            }
+
+            Formula::Empty | Formula::Number(_) | Formula::Text(_) | Formula::Invalid(_) => {}
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Function {
+    Sum,
+    Prod,
+    Avg,
+    Min,
+    Max,
+    Count,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Function> {
+        match name.to_lowercase().as_str() {
+            "sum" => Some(Function::Sum),
+            "prod" => Some(Function::Prod),
+            "avg" => Some(Function::Avg),
+            "min" => Some(Function::Min),
+            "max" => Some(Function::Max),
+            "count" => Some(Function::Count),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Cell {
+    pub content: String,
+    pub formula: Formula,
+    pub value: CellValue,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sheet {
+    cells: HashMap<CellId, Cell>,
+    dependents: HashMap<CellId, BTreeSet<CellId>>,
+}
+
+impl Sheet {
+    pub fn new() -> Sheet {
+        Sheet::default()
+    }
+
+    pub fn content(&self, id: &CellId) -> &str {
+        self.cells
+            .get(id)
+            .map(|cell| cell.content.as_str())
+            .unwrap_or("")
+    }
+
+    pub fn value(&self, id: &CellId) -> CellValue {
+        self.cells
+            .get(id)
+            .map(|cell| cell.value.clone())
+            .unwrap_or(CellValue::Empty)
+    }
+
+    pub fn dependents(&self, id: &CellId) -> BTreeSet<CellId> {
+        self.dependents.get(id).cloned().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, id: CellId, content: &str) {
+        if let Some(old) = self.cells.remove(&id) {
+            for dependency in old.formula.references() {
+                self.remove_dependent(&dependency, &id);
+            }
+        }
+
+        let formula = Formula::parse(content);
+
+        for dependency in formula.references() {
+            self.dependents.entry(dependency).or_default().insert(id);
+        }
+
+        if formula != Formula::Empty {
+            self.cells.insert(
+                id,
+                Cell {
+                    content: content.to_string(),
+                    formula,
+                    value: CellValue::Empty,
+                },
+            );
+        }
+
+        self.recompute(id);
+    }
+
+    fn remove_dependent(&mut self, dependency: &CellId, dependent: &CellId) {
+        if let Some(dependents) = self.dependents.get_mut(dependency) {
+            dependents.remove(dependent);
+
+            if dependents.is_empty() {
+                self.dependents.remove(dependency);
+            }
+        }
+    }
+
+    // Recomputes the changed cell and everything that transitively depends on it.
+    // Cells are evaluated in dependency order so long chains don't recurse, any left over are part of
+    // a cycle or depend on one.
+    fn recompute(&mut self, changed: CellId) {
+        let mut affected = HashSet::new();
+        let mut queue = vec![changed];
+
+        while let Some(id) = queue.pop() {
+            if affected.insert(id) {
+                queue.extend(self.dependents(&id));
+            }
+        }
+
+        // How many affected cells each affected cell is still waiting for
+        let mut waiting: HashMap<CellId, usize> = affected
+            .iter()
+            .map(|id| {
+                let count = self
+                    .cells
+                    .get(id)
+                    .map(|cell| {
+                        cell.formula
+                            .references()
+                            .iter()
+                            .filter(|dependency| affected.contains(dependency))
+                            .count()
+                    })
+                    .unwrap_or(0);
+
+                (*id, count)
+            })
+            .collect();
+
+        let mut ready: Vec<CellId> = waiting
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(id, _)| *id)
+            .collect();
+
+        while let Some(id) = ready.pop() {
+            waiting.remove(&id);
+            self.evaluate_cell(&id);
+
+            for dependent in self.dependents(&id) {
+                if let Some(count) = waiting.get_mut(&dependent) {
+                    *count -= 1;
+
+                    if *count == 0 {
+                        ready.push(dependent);
+                    }
+                }
+            }
+        }
+
+        for id in waiting.keys() {
+            if let Some(cell) = self.cells.get_mut(id) {
+                cell.value = CellValue::Error(CellError::Cycle);
+            }
+        }
+    }
+
+    fn evaluate_cell(&mut self, id: &CellId) {
+        let value = match self.cells.get(id) {
+            Some(cell) => self.evaluate(&cell.formula),
+            None => return,
+        };
+
+        if let Some(cell) = self.cells.get_mut(id) {
+            cell.value = value;
+        }
+    }
+
+    fn evaluate(&self, formula: &Formula) -> CellValue {
+        match formula {
+            Formula::Empty => CellValue::Empty,
+
+            Formula::Number(n) => CellValue::Number(*n),
+
+            Formula::Text(s) => CellValue::Text(s.clone()),
+
+            Formula::Ref(id) => self.value(id),
+
+            Formula::Range(_, _) => CellValue::Error(CellError::Value),
+
+            Formula::Invalid(_) => CellValue::Error(CellError::Parse),
+
+            Formula::Negate(formula) => {
+                let value = self.evaluate(formula);
+                match to_number(&value) {
+                    Ok(n) => CellValue::Number(-n),
+                    Err(err) => CellValue::Error(err),
+                }
+            }
+
+            Formula::Binary(op, left, right) => {
+                let left = self.evaluate(left);
+                let right = self.evaluate(right);
+
+                match (to_number(&left), to_number(&right)) {
+                    (Ok(a), Ok(b)) => apply_operator(*op, a, b),
+                    (Err(err), _) | (_, Err(err)) => CellValue::Error(err),
+                }
+            }
+
+            Formula::Call(function, args) => {
+                let mut numbers = vec![];
+
+                for arg in args {
+                    let values = match arg {
+                        Formula::Range(from, to) => range_cells(from, to)
+                            .iter()
+                            .map(|id| self.value(id))
+                            .collect(),
+
+                        _ => vec![self.evaluate(arg)],
+                    };
+
+                    for value in values {
+                        match value {
+                            CellValue::Empty => {}
+                            CellValue::Number(n) => numbers.push(n),
+                            CellValue::Text(_) => return CellValue::Error(CellError::Value),
+                            CellValue::Error(err) => return CellValue::Error(err),
+                        }
+                    }
+                }
+
+                apply_function(*function, &numbers)
+            }
+        }
+    }
+}
+
+fn to_number(value: &CellValue) -> Result<f64, CellError> {
+    match value {
+        CellValue::Empty => Ok(0.0),
+        CellValue::Number(n) => Ok(*n),
+        CellValue::Text(_) => Err(CellError::Value),
+        CellValue::Error(err) => Err(*err),
+    }
+}
+
+fn apply_operator(op: Operator, a: f64, b: f64) -> CellValue {
+    let result = match op {
+        Operator::Add => a + b,
+        Operator::Subtract => a - b,
+        Operator::Multiply => a * b,
+        Operator::Divide => {
+            if b == 0.0 {
+                return CellValue::Error(CellError::DivisionByZero);
+            }
+
+            a / b
+        }
+    };
+
+    finite_number(result)
+}
+
+fn apply_function(function: Function, numbers: &[f64]) -> CellValue {
+    match function {
+        Function::Sum => finite_number(numbers.iter().sum()),
+
+        Function::Prod => finite_number(numbers.iter().product()),
+
+        Function::Count => CellValue::Number(numbers.len() as f64),
+
+        Function::Avg => {
+            if numbers.is_empty() {
+                CellValue::Error(CellError::DivisionByZero)
+            } else {
+                finite_number(numbers.iter().sum::<f64>() / numbers.len() as f64)
+            }
+        }
+
+        Function::Min => numbers
+            .iter()
+            .copied()
+            .reduce(f64::min)
+            .map(CellValue::Number)
+            .unwrap_or(CellValue::Error(CellError::Value)),
+
+        Function::Max => numbers
+            .iter()
+            .copied()
+            .reduce(f64::max)
+            .map(CellValue::Number)
+            .unwrap_or(CellValue::Error(CellError::Value)),
+    }
+}
+
+// Non-finite numbers can't be serialized to json, so they are reported as errors
+fn finite_number(n: f64) -> CellValue {
+    if n.is_finite() {
+        CellValue::Number(n)
+    } else {
+        CellValue::Error(CellError::Value)
+    }
+}
+
+fn range_cells(from: &CellId, to: &CellId) -> Vec<CellId> {
+    let columns = from.column.min(to.column)..=from.column.max(to.column);
+    let rows = from.row.min(to.row)..=from.row.max(to.row);
+
+    columns
+        .flat_map(|column| rows.clone().map(move |row| CellId { column, row }))
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Colon,
+    Comma,
+    LeftParen,
+    RightParen,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' | '\t' => {
+                chars.next();
+            }
+
+            '+' | '-' | '*' | '/' | ':' | ',' | '(' | ')' => {
+                chars.next();
+                tokens.push(match c {
+                    '+' => Token::Plus,
+                    '-' => Token::Minus,
+                    '*' => Token::Star,
+                    '/' => Token::Slash,
+                    ':' => Token::Colon,
+                    ',' => Token::Comma,
+                    '(' => Token::LeftParen,
+                    _ => Token::RightParen,
+                });
+            }
+
+            '0'..='9' | '.' => {
+                let mut s = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_digit() || c == '.' {
+                        s.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+
+                let n = s.parse().map_err(|_| format!("Invalid number: {}", s))?;
+                tokens.push(Token::Number(n));
+            }
+
+            c if c.is_ascii_alphabetic() => {
+                let mut s = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() {
+                        s.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+
+                tokens.push(Token::Ident(s));
+            }
+
+            _ => {
+                return Err(format!("Unexpected character: {}", c));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+// Deeper formulas are rejected, so parsing and evaluating them can't run out of stack
+const MAX_DEPTH: usize = 256;
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    // How many parentheses, negations and calls the parser is inside of
+    nesting: usize,
+}
+
+impl Parser {
+    fn parse(input: &str) -> Result<Formula, String> {
+        let mut parser = Parser {
+            tokens: tokenize(input)?,
+            pos: 0,
+            nesting: 0,
+        };
+
+        let (formula, _) = parser.expression()?;
+
+        match parser.peek() {
+            None => Ok(formula),
+            Some(token) => Err(format!("Unexpected token: {:?}", token)),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("Expected {:?}, got {:?}", expected, token)),
+            None => Err(format!("Expected {:?}", expected)),
+        }
+    }
+
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Parser) -> Result<T, String>,
+    ) -> Result<T, String> {
+        if self.nesting == MAX_DEPTH {
+            return Err("Formula is nested too deeply".to_string());
+        }
+
+        self.nesting += 1;
+        let result = parse(self);
+        self.nesting -= 1;
+
+        result
+    }
+
+    // The depth of a node above children of the given depth
+    fn node_depth(child_depth: usize) -> Result<usize, String> {
+        if child_depth >= MAX_DEPTH {
+            Err("Formula is nested too deeply".to_string())
+        } else {
+            Ok(child_depth + 1)
+        }
+    }
+
+    // Each parse step returns the formula with its depth, which long chains like 1+1+1 add to
+    fn expression(&mut self) -> Result<(Formula, usize), String> {
+        let (mut left, mut depth) = self.term()?;
+
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => Operator::Add,
+                Some(Token::Minus) => Operator::Subtract,
+                _ => return Ok((left, depth)),
+            };
+
+            self.next();
+            let (right, right_depth) = self.term()?;
+            depth = Parser::node_depth(depth.max(right_depth))?;
+            left = Formula::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn term(&mut self) -> Result<(Formula, usize), String> {
+        let (mut left, mut depth) = self.factor()?;
+
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => Operator::Multiply,
+                Some(Token::Slash) => Operator::Divide,
+                _ => return Ok((left, depth)),
+            };
+
+            self.next();
+            let (right, right_depth) = self.factor()?;
+            depth = Parser::node_depth(depth.max(right_depth))?;
+            left = Formula::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn factor(&mut self) -> Result<(Formula, usize), String> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok((Formula::Number(n), 0)),
+
+            Some(Token::Minus) => {
+                let (formula, depth) = self.nested(Parser::factor)?;
+                Ok((
+                    Formula::Negate(Box::new(formula)),
+                    Parser::node_depth(depth)?,
+                ))
+            }
+
+            Some(Token::LeftParen) => {
+                let formula = self.nested(Parser::expression)?;
+                self.expect(Token::RightParen)?;
+                Ok(formula)
+            }
+
+            Some(Token::Ident(name)) => {
+                if self.peek() == Some(&Token::LeftParen) {
+                    self.nested(|parser| parser.call(&name))
+                } else {
+                    Ok((self.reference(&name)?, 0))
+                }
+            }
+
+            Some(token) => Err(format!("Unexpected token: {:?}", token)),
+
+            None => Err("Unexpected end of formula".to_string()),
+        }
+    }
+
+    fn call(&mut self, name: &str) -> Result<(Formula, usize), String> {
+        let function =
+            Function::from_name(name).ok_or_else(|| format!("Unknown function: {}", name))?;
+
+        self.expect(Token::LeftParen)?;
+
+        let mut args = vec![];
+        let mut depth = 0;
+
+        if self.peek() != Some(&Token::RightParen) {
+            loop {
+                let (arg, arg_depth) = self.expression()?;
+                args.push(arg);
+                depth = depth.max(arg_depth);
+
+                if self.peek() == Some(&Token::Comma) {
+                    self.next();
+                } else {
+                    break;
+                }
+            }
+        }
+
+        self.expect(Token::RightParen)?;
+
+        Ok((Formula::Call(function, args), Parser::node_depth(depth)?))
+    }
+
+    fn reference(&mut self, name: &str) -> Result<Formula, String> {
+        let from =
+            CellId::parse(name).ok_or_else(|| format!("Invalid cell reference: {}", name))?;
+
+        if self.peek() != Some(&Token::Colon) {
+            return Ok(Formula::Ref(from));
+        }
+
+        self.next();
+
+        match self.next() {
+            Some(Token::Ident(name)) => {
+                let to = CellId::parse(&name)
+                    .ok_or_else(|| format!("Invalid cell reference: {}", name))?;
+                Ok(Formula::Range(from, to))
+            }
+
+            _ => Err("Expected cell reference after ':'".to_string()),
+        }
+    }
+}
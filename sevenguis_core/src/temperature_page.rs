@@ -1,3 +1,12 @@
+use crate::components::Field;
+use crate::components::Input;
+use crate::hydration::Validate;
+use crate::layout;
+use crate::page_error;
+use crate::page_error::ErrorState;
+use crate::page_error::PageError;
+use crate::registry;
+use crate::runtime;
 use maud::html;
 use polyester::browser;
 use polyester::browser::DomId;
@@ -7,11 +16,24 @@ use polyester::page::Page;
 use polyester::page::PageMarkup;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
     pub celsius: Option<i32>,
     pub fahrenheit: Option<i32>,
+    pub error: Option<PageError>,
+}
+
+impl Validate for Model {}
+
+impl ErrorState for Model {
+    fn error(&self) -> Option<&PageError> {
+        self.error.as_ref()
+    }
+
+    fn set_error(&mut self, error: Option<PageError>) {
+        self.error = error;
+    }
 }
 
 pub struct TemperaturePage {}
@@ -25,6 +47,7 @@ impl Page<Model, Msg, AppEffect> for TemperaturePage {
         let model = Model {
             celsius: None,
             fahrenheit: None,
+            error: None,
         };
 
         let effects = vec![];
@@ -36,15 +59,33 @@ impl Page<Model, Msg, AppEffect> for TemperaturePage {
         vec![
             browser::on_input(&Id::Celsius, Msg::CelsiusChanged),
             browser::on_input(&Id::Fahrenheit, Msg::FahrenheitChanged),
+            browser::on_click(&page_error::Id::DismissError, Msg::ErrorDismissed),
         ]
     }
 
     fn update(&self, msg: &Msg, model: &mut Model) -> Result<Effects<Msg, AppEffect>, String> {
+        runtime::update(model, |model| self.try_update(msg, model))
+    }
+
+    fn view(&self, model: &Model) -> PageMarkup {
+        PageMarkup {
+            head: view_head(),
+            body: view_body(&self.id(), model),
+        }
+    }
+}
+
+impl TemperaturePage {
+    fn try_update(
+        &self,
+        msg: &Msg,
+        model: &mut Model,
+    ) -> Result<Effects<Msg, AppEffect>, PageError> {
         match msg {
             Msg::CelsiusChanged(value) => {
                 let str_value: String = value
                     .parse()
-                    .map_err(|err| format!("Failed to parse celsius as string: {}", err))?;
+                    .map_err(|err| PageError::parse("celsius", err))?;
 
                 match str_value.parse() {
                     Ok(celsius) => {
@@ -61,7 +102,7 @@ impl Page<Model, Msg, AppEffect> for TemperaturePage {
             Msg::FahrenheitChanged(value) => {
                 let str_value: String = value
                     .parse()
-                    .map_err(|err| format!("Failed to parse celsius as string: {}", err))?;
+                    .map_err(|err| PageError::parse("fahrenheit", err))?;
 
                 match str_value.parse() {
                     Ok(fahrenheit) => {
@@ -74,20 +115,18 @@ impl Page<Model, Msg, AppEffect> for TemperaturePage {
 
                 Ok(vec![])
             }
-        }
-    }
 
-    fn view(&self, model: &Model) -> PageMarkup {
-        PageMarkup {
-            head: view_head(),
-            body: view_body(&self.id(), model),
+            Msg::ErrorDismissed => {
+                model.error = None;
+                Ok(vec![])
+            }
         }
     }
 }
 
 #[derive(strum_macros::Display, polyester_macro::ToDomId)]
 #[strum(serialize_all = "kebab-case")]
-enum Id {
+pub enum Id {
     Celsius,
     Fahrenheit,
 }
@@ -97,6 +136,7 @@ enum Id {
 pub enum Msg {
     CelsiusChanged(String),
     FahrenheitChanged(String),
+    ErrorDismissed,
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
@@ -104,40 +144,28 @@ pub enum Msg {
 pub enum AppEffect {}
 
 fn view_head() -> maud::Markup {
-    html! {
-        title { "Temperature converter - polyester" }
-        link rel="stylesheet" href="/app.css";
-        script defer type="module" src="/temperature_page.js" {}
-    }
+    layout::view_head(&registry::TEMPERATURE)
 }
 
 fn view_body(page_id: &browser::DomId, model: &Model) -> maud::Markup {
     let celsius = model.celsius.map(|n| n.to_string()).unwrap_or_default();
     let fahrenheit = model.fahrenheit.map(|n| n.to_string()).unwrap_or_default();
 
-    html! {
-        div id=(page_id) {
+    let content = html! {
+        div {
             div class="flex p-4" {
-                div {
-                    label class="block text-sm font-medium text-gray-700" for=(Id::Celsius) {
-                        "Celsius"
-                    }
-                    div class="mt-1" {
-                        input id=(Id::Celsius) value=(celsius) class="shadow-sm focus:ring-indigo-500 focus:border-indigo-500 block w-full sm:text-sm border-gray-300 rounded-md" type="number";
-                    }
-                }
-
-                div class="ml-4"{
-                    label class="block text-sm font-medium text-gray-700" for=(Id::Fahrenheit) {
-                        "Fahrenheit"
-                    }
-                    div class="mt-1" {
-                        input id=(Id::Fahrenheit) value=(fahrenheit) class="shadow-sm focus:ring-indigo-500 focus:border-indigo-500 block w-full sm:text-sm border-gray-300 rounded-md" type="number";
-                    }
-                }
+                (Field::new("Celsius", Input::number(Id::Celsius, celsius).class("w-full")))
+                (Field::new("Fahrenheit", Input::number(Id::Fahrenheit, fahrenheit).class("w-full")).class("ml-4"))
             }
         }
-    }
+    };
+
+    layout::view_body(
+        page_id,
+        &registry::TEMPERATURE,
+        model.error.as_ref(),
+        content,
+    )
 }
 
 fn celsius_to_fahrenheit(celsius: i32) -> i32 {
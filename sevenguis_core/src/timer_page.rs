@@ -1,6 +1,23 @@
+use crate::clock::Clock;
+use crate::components::Button;
+use crate::components::Field;
+use crate::components::Input;
+use crate::components::Label;
+use crate::components::Select;
+use crate::duration;
+use crate::hydration;
+use crate::hydration::Validate;
+use crate::laps;
+use crate::layout;
+use crate::page_error;
+use crate::page_error::ErrorState;
+use crate::page_error::PageError;
+use crate::registry;
+use crate::runtime;
 use maud::html;
 use polyester::browser;
 use polyester::browser::time as time_effect;
+use polyester::browser::to_value;
 use polyester::browser::DomId;
 use polyester::browser::Effects;
 use polyester::browser::ToDomId;
@@ -9,19 +26,192 @@ use polyester::page::Page;
 use polyester::page::PageMarkup;
 use polyester::time;
 use serde::{Deserialize, Serialize};
+use std::rc::Rc;
 use std::time::Duration;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
     pub current_time: time::Posix,
-    pub previous_time: time::Posix,
+    pub timers: Vec<Timer>,
+    pub next_id: u32,
+    // The longest duration the sliders reach, longer ones can still be typed
+    pub slider_max: Duration,
+    pub slider_max_input: String,
+    // Whether the tab is showing, as reported by the page visibility api
+    pub visible: bool,
+    pub error: Option<PageError>,
+}
+
+impl Validate for Model {
+    fn validate(&self) -> Result<(), String> {
+        hydration::validate_ids(self.timers.iter().map(|timer| timer.id), self.next_id)?;
+
+        if self.slider_max.is_zero() {
+            return Err("Slider range is zero".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl Model {
+    fn add_timer(&mut self, now: time::Posix) -> Result<(), PageError> {
+        let next_id = self
+            .next_id
+            .checked_add(1)
+            .ok_or_else(|| PageError::invalid_state("add timer", "No ids left for new timers"))?;
+
+        self.timers.push(Timer::new(self.next_id, now));
+        self.next_id = next_id;
+
+        Ok(())
+    }
+
+    fn find_timer_mut(&mut self, id: u32, context: &str) -> Result<&mut Timer, PageError> {
+        self.timers
+            .iter_mut()
+            .find(|timer| timer.id == id)
+            .ok_or_else(|| PageError::invalid_state(context, "Failed to find timer"))
+    }
+}
+
+impl ErrorState for Model {
+    fn error(&self) -> Option<&PageError> {
+        self.error.as_ref()
+    }
+
+    fn set_error(&mut self, error: Option<PageError>) {
+        self.error = error;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Mode {
+    CountUp,
+    // Shows the time left and announces when it runs out
+    Countdown,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Timer {
+    pub id: u32,
+    pub name: String,
+    pub mode: Mode,
     pub max_duration: std::time::Duration,
-    pub elapsed: std::time::Duration,
+    // As typed, which may not be a valid duration yet
+    pub duration_input: String,
+    // Time counted up to when the timer was last started
+    pub accumulated: std::time::Duration,
+    // When the timer was last started or resumed, none while it's paused
+    pub started_at: Option<time::Posix>,
+    // Elapsed time when each lap was recorded
+    pub laps: Vec<Duration>,
+    // Whether the timer was finished as of the previous update, so completion is only announced once
+    pub completed: bool,
+}
+
+impl Timer {
+    fn new(id: u32, now: time::Posix) -> Timer {
+        Timer {
+            id,
+            name: format!("Timer {}", id),
+            mode: Mode::CountUp,
+            max_duration: Duration::from_secs(15),
+            duration_input: duration::format_input(Duration::from_secs(15)),
+            accumulated: Duration::from_secs(0),
+            started_at: Some(now),
+            laps: vec![],
+            completed: false,
+        }
+    }
+
+    // Measured from the timestamps rather than summed per tick, so late or missed ticks can't cause drift
+    pub fn elapsed(&self, now: time::Posix) -> Duration {
+        let running = self
+            .started_at
+            .map(|started_at| duration_between(started_at, now))
+            .unwrap_or_default();
+
+        (self.accumulated + running).min(self.max_duration)
+    }
+
+    pub fn remaining(&self, now: time::Posix) -> Duration {
+        self.max_duration - self.elapsed(now)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.started_at.is_none()
+    }
+
+    pub fn is_finished(&self, now: time::Posix) -> bool {
+        self.elapsed(now) >= self.max_duration
+    }
+
+    pub fn is_running(&self, now: time::Posix) -> bool {
+        !self.is_paused() && !self.is_finished(now)
+    }
+
+    fn pause(&mut self, now: time::Posix) {
+        self.accumulated = self.elapsed(now);
+        self.started_at = None;
+    }
+
+    fn resume(&mut self, now: time::Posix) {
+        self.started_at = Some(now);
+    }
+
+    fn reset(&mut self, now: time::Posix) {
+        self.accumulated = Duration::from_secs(0);
+        self.laps.clear();
+
+        if !self.is_paused() {
+            self.started_at = Some(now);
+        }
+    }
+
+    // Keeps the elapsed time as it is, so a finished timer doesn't count the time since it finished
+    fn set_max_duration(&mut self, max_duration: Duration, now: time::Posix) {
+        self.accumulated = self.elapsed(now).min(max_duration);
+        self.max_duration = max_duration;
+
+        if !self.is_paused() {
+            self.started_at = Some(now);
+        }
+    }
+}
+
+fn duration_between(start: time::Posix, end: time::Posix) -> Duration {
+    let millis = end.as_millis().saturating_sub(start.as_millis()).max(0);
+
+    Duration::from_millis(u64::try_from(millis).unwrap_or(u64::MAX))
+}
+
+const DEFAULT_SLIDER_MAX: Duration = Duration::from_secs(30);
+
+const VISIBLE_TICK_INTERVAL: Duration = Duration::from_millis(200);
+// Browsers throttle hidden tabs to about once a second anyway
+const HIDDEN_TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+// How often the page needs to hear the time, if at all. Paused and finished timers look the same
+// from one second to the next, and while the tab is hidden only a countdown needs to notice it ran out.
+pub fn tick_interval(model: &Model) -> Option<Duration> {
+    let now = model.current_time;
+    let mut running = model.timers.iter().filter(|timer| timer.is_running(now));
+
+    if model.visible {
+        running.next().map(|_| VISIBLE_TICK_INTERVAL)
+    } else {
+        running
+            .any(|timer| timer.mode == Mode::Countdown)
+            .then_some(HIDDEN_TICK_INTERVAL)
+    }
 }
 
 pub struct TimerPage {
-    pub initial_time: time::Posix,
+    pub clock: Rc<dyn Clock>,
 }
 
 impl Page<Model, Msg, AppEffect> for TimerPage {
@@ -30,171 +220,617 @@ impl Page<Model, Msg, AppEffect> for TimerPage {
     }
 
     fn init(&self) -> (Model, Effects<Msg, AppEffect>) {
+        let now = self.clock.now();
+
         let model = Model {
-            current_time: self.initial_time,
-            previous_time: self.initial_time,
-            max_duration: Duration::from_secs(15),
-            elapsed: Duration::from_secs(0),
+            current_time: now,
+            timers: vec![Timer::new(1, now)],
+            next_id: 2,
+            slider_max: DEFAULT_SLIDER_MAX,
+            slider_max_input: duration::format_input(DEFAULT_SLIDER_MAX),
+            visible: true,
+            error: None,
         };
 
-        let effects = vec![];
+        let effects = vec![browser::app_effect(AppEffect::LoadTimers)];
 
         (model, effects)
     }
 
     fn subscriptions(&self, model: &Model) -> browser::Subscriptions<Msg, AppEffect> {
-        vec![
-            browser::on_input(&Id::Duration, Msg::MaxDurationChanged),
-            browser::on_click(&Id::Reset, Msg::ResetClicked),
-            browser::interval_effect(
-                Duration::from_millis(100),
-                Msg::GotTime,
-                time_effect::current_time(),
-            ),
-            if model.elapsed < model.max_duration {
-                browser::interval_effect(
-                    Duration::from_millis(200),
-                    Msg::OnTick,
-                    time_effect::current_time(),
-                )
-            } else {
-                browser::no_subscription()
+        let mut subscriptions = vec![
+            browser::on_click(&Id::AddTimer, Msg::AddTimerClicked),
+            browser::on_input(&Id::SliderMax, Msg::SliderMaxChanged),
+            browser::on_click(&page_error::Id::DismissError, Msg::ErrorDismissed),
+            // A single tick drives every timer
+            match tick_interval(model) {
+                Some(interval) => {
+                    browser::interval_effect(interval, Msg::OnTick, time_effect::current_time())
+                }
+
+                None => browser::no_subscription(),
             },
-        ]
+        ];
+
+        subscriptions.extend(model.timers.iter().flat_map(timer_subscriptions));
+
+        subscriptions
     }
 
     fn update(&self, msg: &Msg, model: &mut Model) -> Result<Effects<Msg, AppEffect>, String> {
+        runtime::update(model, |model| self.try_update(msg, model))
+    }
+
+    fn view(&self, model: &Model) -> PageMarkup {
+        PageMarkup {
+            head: view_head(),
+            body: view_body(&self.id(), model),
+        }
+    }
+}
+
+fn timer_subscriptions(timer: &Timer) -> browser::Subscriptions<Msg, AppEffect> {
+    let id = timer.id;
+
+    vec![
+        browser::on_input(&timer_dom_id(Id::Name, id), move |name| {
+            Msg::NameChanged(id, name)
+        }),
+        browser::on_input(&timer_dom_id(Id::Duration, id), move |value| {
+            Msg::MaxDurationChanged(id, value)
+        }),
+        browser::on_input(&timer_dom_id(Id::DurationText, id), move |text| {
+            Msg::DurationTextChanged(id, text)
+        }),
+        browser::on_change(&timer_dom_id(Id::Mode, id), move |value| {
+            Msg::ModeChanged(id, value)
+        }),
+        if timer.is_paused() {
+            browser::on_click(&timer_dom_id(Id::PauseResume, id), Msg::ResumeClicked(id))
+        } else {
+            browser::on_click(&timer_dom_id(Id::PauseResume, id), Msg::PauseClicked(id))
+        },
+        browser::on_click(&timer_dom_id(Id::Lap, id), Msg::LapClicked(id)),
+        browser::on_click(&timer_dom_id(Id::Reset, id), Msg::ResetClicked(id)),
+        browser::on_click(
+            &timer_dom_id(Id::ExportLaps, id),
+            Msg::ExportLapsClicked(id),
+        ),
+        browser::on_click(&timer_dom_id(Id::Remove, id), Msg::RemoveClicked(id)),
+    ]
+}
+
+impl TimerPage {
+    fn try_update(
+        &self,
+        msg: &Msg,
+        model: &mut Model,
+    ) -> Result<Effects<Msg, AppEffect>, PageError> {
+        // Ticks carry the browser's time, everything else reads the clock
+        let now = match msg {
+            Msg::OnTick(value) => parse_current_time(value)?,
+            _ => self.clock.now(),
+        };
+
+        let mut effects = self.apply(msg, model, now)?;
+        model.current_time = now;
+        let completed = announce_completed(model, now);
+
+        // Ticks only change what's shown, unless they finish a countdown
+        if changes_timers(msg) || !completed.is_empty() {
+            effects.push(save_timers(model));
+        }
+
+        effects.extend(completed);
+
+        Ok(effects)
+    }
+
+    fn apply(
+        &self,
+        msg: &Msg,
+        model: &mut Model,
+        now: time::Posix,
+    ) -> Result<Effects<Msg, AppEffect>, PageError> {
         match msg {
-            Msg::MaxDurationChanged(value) => {
-                if model.elapsed >= model.max_duration {
-                    model.previous_time = model.current_time;
+            // Elapsed time follows from the current time, so a tick only needs to render
+            Msg::OnTick(_) => Ok(vec![]),
+
+            Msg::VisibilityChanged(visible) => {
+                model.visible = *visible;
+                Ok(vec![])
+            }
+
+            Msg::TimersLoaded(data) => {
+                // Missing or corrupt data leaves the new timer in place
+                if let Some(stored) = data.as_deref().and_then(|s| StoredTimers::decode(s).ok()) {
+                    model.next_id = stored.next_id;
+                    model.slider_max = stored.slider_max;
+                    model.slider_max_input = duration::format_input(stored.slider_max);
+                    model.timers = stored
+                        .timers
+                        .into_iter()
+                        .map(|timer| restore_timer(timer, stored.saved_at, now))
+                        .collect();
                 }
 
+                Ok(vec![])
+            }
+
+            Msg::AddTimerClicked => {
+                model.add_timer(now)?;
+                Ok(vec![])
+            }
+
+            Msg::RemoveClicked(id) => {
+                model.find_timer_mut(*id, "remove timer")?;
+                model.timers.retain(|timer| timer.id != *id);
+
+                Ok(vec![])
+            }
+
+            Msg::NameChanged(id, name) => {
+                let timer = model.find_timer_mut(*id, "rename timer")?;
+                timer.name = name.clone();
+
+                Ok(vec![])
+            }
+
+            Msg::MaxDurationChanged(id, value) => {
+                let timer = model.find_timer_mut(*id, "max duration")?;
+
                 let max_duration: u64 = value
                     .parse()
-                    .map_err(|err| format!("Failed to parse max duration: {}", err))?;
+                    .map_err(|err| PageError::parse("max duration", err))?;
+
+                timer.set_max_duration(Duration::from_millis(max_duration), now);
+                timer.duration_input = duration::format_input(timer.max_duration);
 
-                model.max_duration = Duration::from_millis(max_duration);
-                if model.elapsed > model.max_duration {
-                    model.elapsed = model.max_duration
+                Ok(vec![])
+            }
+
+            Msg::DurationTextChanged(id, text) => {
+                let timer = model.find_timer_mut(*id, "duration")?;
+                timer.duration_input = text.clone();
+
+                // The field shows why the text isn't a duration until it is one
+                if let Ok(max_duration) = duration::parse(text) {
+                    timer.set_max_duration(max_duration, now);
                 }
 
                 Ok(vec![])
             }
 
-            Msg::GotTime(value) => {
-                model.current_time = parse_current_time(value)?;
+            Msg::SliderMaxChanged(text) => {
+                model.slider_max_input = text.clone();
+
+                if let Ok(slider_max) = parse_slider_max(text) {
+                    model.slider_max = slider_max;
+                }
+
                 Ok(vec![])
             }
 
-            Msg::OnTick(value) => {
-                model.current_time = parse_current_time(value)?;
+            Msg::ModeChanged(id, value) => {
+                let timer = model.find_timer_mut(*id, "mode")?;
 
-                let elapsed = model.elapsed + (model.current_time - model.previous_time);
+                let mode = value.parse().map_err(|err| PageError::parse("mode", err))?;
 
-                if elapsed > model.max_duration {
-                    model.elapsed = model.max_duration
+                let effects = if mode == Mode::Countdown && timer.mode != Mode::Countdown {
+                    // Asked for while handling the user's choice, which browsers require
+                    vec![browser::app_effect(
+                        AppEffect::RequestNotificationPermission,
+                    )]
                 } else {
-                    model.elapsed = elapsed;
+                    vec![]
+                };
+
+                timer.mode = mode;
+
+                Ok(effects)
+            }
+
+            Msg::ResetClicked(id) => {
+                let timer = model.find_timer_mut(*id, "reset")?;
+                timer.reset(now);
+
+                Ok(vec![])
+            }
+
+            Msg::PauseClicked(id) => {
+                let timer = model.find_timer_mut(*id, "pause")?;
+
+                if timer.is_paused() {
+                    return Err(PageError::invalid_state(
+                        "pause",
+                        "The timer is already paused",
+                    ));
                 }
 
-                model.previous_time = model.current_time;
+                timer.pause(now);
 
                 Ok(vec![])
             }
 
-            Msg::ResetClicked => {
-                model.elapsed = Duration::from_secs(0);
-                model.previous_time = model.current_time;
+            Msg::ResumeClicked(id) => {
+                let timer = model.find_timer_mut(*id, "resume")?;
+
+                if !timer.is_paused() {
+                    return Err(PageError::invalid_state("resume", "The timer isn't paused"));
+                }
+
+                timer.resume(now);
 
                 Ok(vec![])
             }
+
+            Msg::LapClicked(id) => {
+                let timer = model.find_timer_mut(*id, "lap")?;
+
+                if !timer.is_running(now) {
+                    return Err(PageError::invalid_state("lap", "The timer isn't running"));
+                }
+
+                timer.laps.push(timer.elapsed(now));
+
+                Ok(vec![])
+            }
+
+            Msg::ExportLapsClicked(id) => {
+                let timer = model.find_timer_mut(*id, "export laps")?;
+
+                if timer.laps.is_empty() {
+                    return Err(PageError::invalid_state(
+                        "export laps",
+                        "No laps are recorded",
+                    ));
+                }
+
+                let effect = browser::app_effect(AppEffect::Download {
+                    filename: format!("laps-{}.csv", timer.id),
+                    mime_type: "text/csv".to_string(),
+                    content: laps::encode_csv(&timer.laps),
+                });
+
+                Ok(vec![effect])
+            }
+
+            Msg::ErrorDismissed => {
+                model.error = None;
+                Ok(vec![])
+            }
         }
     }
+}
 
-    fn view(&self, model: &Model) -> PageMarkup {
-        PageMarkup {
-            head: view_head(),
-            body: view_body(&self.id(), model),
+fn parse_slider_max(text: &str) -> Result<Duration, String> {
+    let slider_max = duration::parse(text)?;
+
+    if slider_max.is_zero() {
+        return Err("The slider range must be longer than zero".to_string());
+    }
+
+    Ok(slider_max)
+}
+
+fn changes_timers(msg: &Msg) -> bool {
+    !matches!(
+        msg,
+        Msg::OnTick(_)
+            | Msg::VisibilityChanged(_)
+            | Msg::TimersLoaded(_)
+            | Msg::ExportLapsClicked(_)
+            | Msg::ErrorDismissed
+    )
+}
+
+// Running timers continue from wall clock time, as if the page had stayed open
+fn restore_timer(mut timer: Timer, saved_at: time::Posix, now: time::Posix) -> Timer {
+    // Nothing can have started after it was saved
+    timer.started_at = timer
+        .started_at
+        .map(|started_at| earliest(started_at, saved_at));
+
+    // The clock has gone back since saving, so there's no telling how long the page was closed
+    if saved_at.as_millis() > now.as_millis() {
+        timer.accumulated = timer.elapsed(saved_at);
+        timer.started_at = timer.started_at.map(|_| now);
+    }
+
+    timer
+}
+
+fn earliest(a: time::Posix, b: time::Posix) -> time::Posix {
+    if a.as_millis() <= b.as_millis() {
+        a
+    } else {
+        b
+    }
+}
+
+fn announce_completed(model: &mut Model, now: time::Posix) -> Effects<Msg, AppEffect> {
+    let mut effects = vec![];
+
+    for timer in &mut model.timers {
+        let finished = timer.is_finished(now);
+
+        if finished && !timer.completed && timer.mode == Mode::Countdown {
+            effects.push(browser::app_effect(AppEffect::Notify {
+                title: timer.name.clone(),
+                body: "Time's up".to_string(),
+            }));
         }
+
+        timer.completed = finished;
     }
+
+    effects
 }
 
-fn parse_current_time(value: &Value) -> Result<time::Posix, String> {
+fn parse_current_time(value: &Value) -> Result<time::Posix, PageError> {
     let current_time = value
         .parse()
-        .map_err(|err| format!("Failed to parse current time: {}", err))?;
+        .map_err(|err| PageError::parse("current time", err))?;
 
     Ok(current_time)
 }
 
 #[derive(strum_macros::Display, polyester_macro::ToDomId)]
 #[strum(serialize_all = "kebab-case")]
-enum Id {
+pub enum Id {
+    AddTimer,
+    Timer,
+    Name,
+    Mode,
     Elapsed,
     Duration,
+    DurationText,
+    SliderMax,
     Reset,
+    PauseResume,
+    Lap,
+    ExportLaps,
+    Laps,
+    Remove,
+}
+
+// Every timer has its own controls, told apart by the timer id
+pub fn timer_dom_id(id: Id, timer_id: u32) -> DomId {
+    DomId::new(&format!("{}-{}", id, timer_id))
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Msg {
-    GotTime(Value),
-    MaxDurationChanged(String),
     OnTick(Value),
-    ResetClicked,
+    VisibilityChanged(bool),
+    TimersLoaded(Option<String>),
+    AddTimerClicked,
+    RemoveClicked(u32),
+    NameChanged(u32, String),
+    MaxDurationChanged(u32, String),
+    DurationTextChanged(u32, String),
+    SliderMaxChanged(String),
+    ModeChanged(u32, Value),
+    ResetClicked(u32),
+    PauseClicked(u32),
+    ResumeClicked(u32),
+    LapClicked(u32),
+    ExportLapsClicked(u32),
+    ErrorDismissed,
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub enum AppEffect {}
+pub enum AppEffect {
+    LoadTimers,
+    SaveTimers(StoredTimers),
+    RequestNotificationPermission,
+    // Shows a browser notification and plays a sound
+    Notify {
+        title: String,
+        body: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Download {
+        filename: String,
+        mime_type: String,
+        content: String,
+    },
+}
 
-fn view_head() -> maud::Markup {
-    html! {
-        title { "Timer" }
-        link rel="stylesheet" href="/app.css";
-        script defer type="module" src="/timer_page.js" {}
+const STORAGE_VERSION: u32 = 1;
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredTimers {
+    pub version: u32,
+    // Lets a reload tell whether the clock has gone back since
+    pub saved_at: time::Posix,
+    pub next_id: u32,
+    pub slider_max: Duration,
+    pub timers: Vec<Timer>,
+}
+
+impl StoredTimers {
+    pub fn new(
+        saved_at: time::Posix,
+        next_id: u32,
+        slider_max: Duration,
+        timers: Vec<Timer>,
+    ) -> StoredTimers {
+        StoredTimers {
+            version: STORAGE_VERSION,
+            saved_at,
+            next_id,
+            slider_max,
+            timers,
+        }
+    }
+
+    fn from_model(model: &Model) -> StoredTimers {
+        StoredTimers::new(
+            model.current_time,
+            model.next_id,
+            model.slider_max,
+            model.timers.clone(),
+        )
     }
+
+    pub fn decode(s: &str) -> Result<StoredTimers, String> {
+        let stored: StoredTimers =
+            serde_json::from_str(s).map_err(|err| format!("Failed to decode timers: {}", err))?;
+
+        if stored.version != STORAGE_VERSION {
+            return Err(format!("Unsupported storage version: {}", stored.version));
+        }
+
+        let mut ids: Vec<u32> = stored.timers.iter().map(|timer| timer.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+
+        if ids.len() != stored.timers.len() {
+            return Err("Stored timers contain duplicate ids".to_string());
+        }
+
+        if stored.slider_max.is_zero() {
+            return Err("Stored slider range is zero".to_string());
+        }
+
+        let max_id = ids.last().copied().unwrap_or(0);
+        let min_next_id = max_id
+            .checked_add(1)
+            .ok_or_else(|| "Stored timers leave no id for the next timer".to_string())?;
+
+        Ok(StoredTimers {
+            next_id: stored.next_id.max(min_next_id),
+            ..stored
+        })
+    }
+}
+
+fn save_timers(model: &Model) -> browser::Effect<Msg, AppEffect> {
+    browser::app_effect(AppEffect::SaveTimers(StoredTimers::from_model(model)))
+}
+
+fn view_head() -> maud::Markup {
+    layout::view_head(&registry::TIMER)
 }
 
 fn view_body(page_id: &browser::DomId, model: &Model) -> maud::Markup {
-    let max_elapsed = model.max_duration.as_millis();
-    let elapsed = model.elapsed.as_millis();
-    let elapsed_text = format!(
-        "{:.2}s / {:.2}s",
-        model.elapsed.as_secs_f64(),
-        model.max_duration.as_secs_f64()
-    );
-    let max_duration = model.max_duration.as_millis();
+    let slider_max_error = parse_slider_max(&model.slider_max_input).err();
 
-    html! {
-        div id=(page_id) {
+    let content = html! {
+        div {
             div class="p-4" {
-                div {
-                    label for=(Id::Elapsed) {
-                        "Elapsed time"
+                @if model.timers.is_empty() {
+                    p class="text-sm text-gray-500" { "No timers" }
+                }
+
+                div class="flex flex-wrap gap-4" {
+                    @for timer in &model.timers {
+                        (view_timer(timer, model.slider_max, model.current_time))
                     }
+                }
+
+                div class="mt-4 flex items-end gap-4" {
+                    (Button::primary(Id::AddTimer, "Add timer").class("w-32"))
+                    (Field::new("Slider range", Input::text(Id::SliderMax, &model.slider_max_input).class("w-32").invalid(slider_max_error.is_some())).error(slider_max_error))
+                }
+            }
+        }
+    };
+
+    layout::view_body(page_id, &registry::TIMER, model.error.as_ref(), content)
+}
+
+fn view_timer(timer: &Timer, slider_max: Duration, now: time::Posix) -> maud::Markup {
+    let id = timer.id;
+    let max_elapsed = timer.max_duration.as_millis();
+    let elapsed = timer.elapsed(now);
+    let is_countdown = timer.mode == Mode::Countdown;
+    let elapsed_text = if is_countdown {
+        format!("{} left", duration::format_display(timer.remaining(now)))
+    } else {
+        format!(
+            "{} / {}",
+            duration::format_display(elapsed),
+            duration::format_display(timer.max_duration)
+        )
+    };
+    let duration_error = duration::parse(&timer.duration_input).err();
+    // Stretched to fit a typed duration beyond the usual range
+    let slider_max =
+        i64::try_from(slider_max.max(timer.max_duration).as_millis()).unwrap_or(i64::MAX);
+    let mode_select = Select::new(timer_dom_id(Id::Mode, id))
+        .option(to_value(Mode::CountUp), "Count up", !is_countdown)
+        .option(to_value(Mode::Countdown), "Countdown", is_countdown);
+    let max_duration = timer.max_duration.as_millis();
+    let pause_label = if timer.is_paused() { "Resume" } else { "Pause" };
+
+    html! {
+        div id=(timer_dom_id(Id::Timer, id)) class="rounded-md border border-gray-200 p-4" {
+            (Field::new("Name", Input::text(timer_dom_id(Id::Name, id), &timer.name).class("w-full")))
+            (Field::new("Mode", mode_select).class("mt-4"))
+
+            div class="mt-4" {
+                @if is_countdown {
+                    (Label::new(timer_dom_id(Id::Elapsed, id), "Remaining time"))
                     div {
-                        meter id=(Id::Elapsed) min="0" max=(max_elapsed) value=(elapsed) class="w-32" {}
+                        meter id=(timer_dom_id(Id::Elapsed, id)) min="0" max=(max_elapsed) value=(timer.remaining(now).as_millis()) class="w-32" {}
                     }
+                } @else {
+                    (Label::new(timer_dom_id(Id::Elapsed, id), "Elapsed time"))
                     div {
-                        (elapsed_text)
+                        meter id=(timer_dom_id(Id::Elapsed, id)) min="0" max=(max_elapsed) value=(elapsed.as_millis()) class="w-32" {}
                     }
                 }
+                div {
+                    (elapsed_text)
+                }
+            }
 
-                div class="mt-4" {
-                    label for=(Id::Duration) {
-                        "Duration"
-                    }
-                    div {
-                        input id=(Id::Duration) type="range" min="0" max="30000" value=(max_duration) class="w-32";
+            (Field::new("Duration", Input::text(timer_dom_id(Id::DurationText, id), &timer.duration_input).class("w-32").invalid(duration_error.is_some())).error(duration_error).class("mt-4"))
+            div class="mt-2" {
+                (Input::range(timer_dom_id(Id::Duration, id), 0, slider_max, max_duration).class("w-32"))
+            }
+
+            div class="mt-4 flex gap-4" {
+                (Button::primary(timer_dom_id(Id::PauseResume, id), pause_label).class("w-24").disabled(!timer.is_paused() && timer.is_finished(now)))
+                (Button::primary(timer_dom_id(Id::Lap, id), "Lap").class("w-24").disabled(!timer.is_running(now)))
+                (Button::primary(timer_dom_id(Id::Reset, id), "Reset").class("w-24"))
+            }
+
+            (view_laps(timer))
+
+            (Button::primary(timer_dom_id(Id::Remove, id), "Remove").class("mt-4 w-24"))
+        }
+    }
+}
+
+fn view_laps(timer: &Timer) -> maud::Markup {
+    html! {
+        div class="mt-4" {
+            table id=(timer_dom_id(Id::Laps, timer.id)) class="text-sm tabular-nums" {
+                thead {
+                    tr {
+                        th class="pr-4 text-left font-medium text-gray-700" { "Lap" }
+                        th class="pr-4 text-right font-medium text-gray-700" { "Delta" }
+                        th class="text-right font-medium text-gray-700" { "Total" }
                     }
                 }
-
-                button id=(Id::Reset) class="mt-4 w-32 text-center items-center px-3 py-2 border border-transparent text-sm leading-4 font-medium rounded-md shadow-sm text-white bg-indigo-600 hover:bg-indigo-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-indigo-500" type="button" {
-                    "Reset"
+                tbody {
+                    @for split in laps::splits(&timer.laps) {
+                        tr data-lap=(split.number) {
+                            td class="pr-4" { (split.number) }
+                            td class="pr-4 text-right" { (duration::format_display(split.delta)) }
+                            td class="text-right" { (duration::format_display(split.total)) }
+                        }
+                    }
                 }
             }
+
+            (Button::primary(timer_dom_id(Id::ExportLaps, timer.id), "Export CSV").class("mt-4 w-32").disabled(timer.laps.is_empty()))
         }
     }
 }
@@ -0,0 +1,90 @@
+mod support;
+
+use sevenguis_core::cells_page::AppEffect;
+use sevenguis_core::cells_page::CellsPage;
+use sevenguis_core::cells_page::Id;
+use sevenguis_core::cells_page::Model;
+use sevenguis_core::cells_page::Msg;
+use sevenguis_core::spreadsheet::CellId;
+use support::Harness;
+
+fn cell(s: &str) -> CellId {
+    CellId::parse(s).unwrap()
+}
+
+fn cell_text(harness: &Harness<CellsPage, Model, Msg, AppEffect>, s: &str) -> String {
+    harness.find_by("data-cell", s).unwrap().text()
+}
+
+#[test]
+fn renders_an_empty_sheet() {
+    let harness = Harness::new(CellsPage {});
+    let html = harness.html();
+
+    assert!(html.contains("data-cell=\"A0\""));
+    assert!(html.contains("data-cell=\"Z99\""));
+    assert_eq!(cell_text(&harness, "A0"), "");
+    assert!(!harness.exists(Id::Editor));
+}
+
+#[test]
+fn editing_shows_the_cell_content() {
+    let mut harness = Harness::new(CellsPage {});
+
+    harness.send_all([
+        Msg::EditStarted(cell("B1")),
+        Msg::EditCommitted("=1+2".to_string()),
+        Msg::EditStarted(cell("B1")),
+    ]);
+
+    assert_eq!(harness.element(Id::Editor).value(), "=1+2");
+}
+
+#[test]
+fn committing_shows_the_value() {
+    let mut harness = Harness::new(CellsPage {});
+
+    harness.send_all([
+        Msg::EditStarted(cell("A0")),
+        Msg::EditCommitted("4".to_string()),
+        Msg::EditStarted(cell("A1")),
+        Msg::EditCommitted("=A0*2".to_string()),
+    ]);
+
+    assert!(!harness.exists(Id::Editor));
+    assert_eq!(cell_text(&harness, "A1"), "8");
+
+    harness.send_all([
+        Msg::EditStarted(cell("A0")),
+        Msg::EditCommitted("5".to_string()),
+    ]);
+
+    assert_eq!(cell_text(&harness, "A1"), "10");
+}
+
+#[test]
+fn cancelling_keeps_the_old_content() {
+    let mut harness = Harness::new(CellsPage {});
+
+    harness.send_all([
+        Msg::EditStarted(cell("C3")),
+        Msg::EditCommitted("hello".to_string()),
+        Msg::EditStarted(cell("C3")),
+        Msg::EditCancelled,
+    ]);
+
+    assert_eq!(harness.model().sheet.content(&cell("C3")), "hello");
+    assert_eq!(cell_text(&harness, "C3"), "hello");
+}
+
+#[test]
+fn errors_are_shown_in_the_cell() {
+    let mut harness = Harness::new(CellsPage {});
+
+    harness.send_all([
+        Msg::EditStarted(cell("A0")),
+        Msg::EditCommitted("=1/0".to_string()),
+    ]);
+
+    assert_eq!(cell_text(&harness, "A0"), "#DIV/0!");
+}
@@ -0,0 +1,135 @@
+mod support;
+
+use sevenguis_core::circle_page::CirclePage;
+use sevenguis_core::circle_page::Id;
+use sevenguis_core::circle_page::Msg;
+use sevenguis_core::circle_page::Point;
+use sevenguis_core::page_error::ErrorKind;
+use support::Harness;
+
+fn point(x: i32, y: i32) -> Point {
+    Point { x, y }
+}
+
+#[test]
+fn clicking_empty_space_creates_a_circle() {
+    let mut harness = Harness::new(CirclePage {});
+
+    harness.send_all([
+        Msg::CanvasClicked(point(50, 50)),
+        Msg::CanvasClicked(point(200, 100)),
+    ]);
+
+    harness.assert_ok();
+    assert_eq!(harness.model().circles.len(), 2);
+    assert_eq!(
+        harness
+            .element(Id::Canvas)
+            .inner_html
+            .matches("<circle")
+            .count(),
+        2
+    );
+    assert!(!harness.element(Id::Undo).is_disabled());
+}
+
+#[test]
+fn clicking_a_circle_selects_it() {
+    let mut harness = Harness::new(CirclePage {});
+
+    harness.send_all([
+        Msg::CanvasClicked(point(50, 50)),
+        Msg::CanvasClicked(point(200, 100)),
+        Msg::CanvasClicked(point(55, 45)),
+    ]);
+
+    assert_eq!(harness.model().circles.len(), 2);
+    assert_eq!(harness.model().selected_id, Some(1));
+}
+
+#[test]
+fn hovering_selects_the_nearest_circle() {
+    let mut harness = Harness::new(CirclePage {});
+
+    harness.send_all([
+        Msg::CanvasClicked(point(50, 50)),
+        Msg::CanvasClicked(point(80, 50)),
+        Msg::PointerMoved(point(67, 50)),
+    ]);
+
+    assert_eq!(harness.model().selected_id, Some(2));
+
+    harness.send(Msg::PointerMoved(point(300, 300)));
+    assert_eq!(harness.model().selected_id, None);
+}
+
+#[test]
+fn right_click_opens_the_diameter_dialog() {
+    let mut harness = Harness::new(CirclePage {});
+
+    assert!(!harness.exists(Id::Diameter));
+
+    harness.send_all([
+        Msg::CanvasClicked(point(50, 50)),
+        Msg::CanvasRightClicked(point(50, 50)),
+    ]);
+
+    assert_eq!(harness.element(Id::Diameter).value(), "30");
+    assert!(harness.contains_text("Adjust diameter of circle at (50, 50)."));
+    assert!(harness.element(Id::Undo).is_disabled());
+}
+
+#[test]
+fn adjusting_the_diameter_is_one_undo_step() {
+    let mut harness = Harness::new(CirclePage {});
+
+    harness.send_all([
+        Msg::CanvasClicked(point(50, 50)),
+        Msg::CanvasRightClicked(point(50, 50)),
+        Msg::DiameterChanged("40".to_string()),
+        Msg::DiameterChanged("60".to_string()),
+        Msg::DialogClosed,
+    ]);
+
+    assert_eq!(harness.model().circles[0].diameter, 60);
+    assert!(!harness.exists(Id::Diameter));
+
+    harness.send(Msg::Undo);
+    assert_eq!(harness.model().circles[0].diameter, 30);
+
+    harness.send(Msg::Redo);
+    assert_eq!(harness.model().circles[0].diameter, 60);
+}
+
+#[test]
+fn undo_removes_a_created_circle() {
+    let mut harness = Harness::new(CirclePage {});
+
+    harness.send_all([Msg::CanvasClicked(point(50, 50)), Msg::Undo]);
+
+    assert!(harness.model().circles.is_empty());
+    assert!(harness.element(Id::Undo).is_disabled());
+    assert!(!harness.element(Id::Redo).is_disabled());
+}
+
+#[test]
+fn a_new_circle_clears_redo() {
+    let mut harness = Harness::new(CirclePage {});
+
+    harness.send_all([
+        Msg::CanvasClicked(point(50, 50)),
+        Msg::Undo,
+        Msg::CanvasClicked(point(100, 100)),
+    ]);
+
+    assert!(harness.element(Id::Redo).is_disabled());
+}
+
+#[test]
+fn diameter_without_a_dialog_is_an_error() {
+    let mut harness = Harness::new(CirclePage {});
+
+    harness.send(Msg::DiameterChanged("40".to_string()));
+
+    assert_eq!(harness.assert_error().kind, ErrorKind::InvalidState);
+}
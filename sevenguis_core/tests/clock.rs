@@ -0,0 +1,68 @@
+use polyester::time::Posix;
+use sevenguis_core::clock;
+use sevenguis_core::clock::Clock;
+use sevenguis_core::clock::ManualClock;
+use std::time::Duration;
+
+// 2022-10-01T12:00:00Z
+const NOON: i128 = 1_664_625_600_000;
+
+#[test]
+fn manual_clock_only_moves_when_advanced() {
+    let clock = ManualClock::new(Posix::from_millis(NOON));
+
+    assert_eq!(clock.now(), Posix::from_millis(NOON));
+    assert_eq!(clock.now(), Posix::from_millis(NOON));
+
+    clock.advance(Duration::from_millis(1500));
+    assert_eq!(clock.now(), Posix::from_millis(NOON + 1500));
+}
+
+#[test]
+fn manual_clock_clones_share_the_time() {
+    let clock = ManualClock::new(Posix::from_millis(NOON));
+    let page_clock = clock.clone();
+
+    clock.set(Posix::from_millis(0));
+
+    assert_eq!(page_clock.now(), Posix::from_millis(0));
+}
+
+#[test]
+fn ticks_return_the_time_of_every_tick() {
+    let clock = ManualClock::new(Posix::from_millis(NOON));
+
+    let ticks = clock.ticks(Duration::from_secs(15), Duration::from_millis(200));
+
+    assert_eq!(ticks.len(), 75);
+    assert_eq!(ticks.first(), Some(&Posix::from_millis(NOON + 200)));
+    assert_eq!(ticks.last(), Some(&Posix::from_millis(NOON + 15_000)));
+    assert_eq!(clock.now(), Posix::from_millis(NOON + 15_000));
+}
+
+#[test]
+fn parses_iso8601() {
+    let cases = [
+        ("2022-10-01T12:00:00Z", NOON),
+        ("2022-10-01T14:00:00+02:00", NOON),
+        ("2022-10-01T12:00:00.250Z", NOON + 250),
+        ("2022-10-01T12:00:00", NOON),
+        ("2022-10-01", NOON - 12 * 60 * 60 * 1000),
+    ];
+
+    for (value, millis) in cases {
+        assert_eq!(
+            clock::parse_iso8601(value),
+            Ok(Posix::from_millis(millis)),
+            "{}",
+            value
+        );
+    }
+}
+
+#[test]
+fn rejects_other_formats() {
+    let err = clock::parse_iso8601("01/10/2022").unwrap_err();
+
+    assert!(err.starts_with("Failed to parse time 01/10/2022"));
+}
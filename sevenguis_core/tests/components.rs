@@ -0,0 +1,100 @@
+mod support;
+
+use maud::Render;
+use sevenguis_core::components::Button;
+use sevenguis_core::components::ErrorMessage;
+use sevenguis_core::components::Field;
+use sevenguis_core::components::Input;
+use sevenguis_core::components::Select;
+use support::Element;
+
+fn render(component: impl Render) -> String {
+    component.render().into_string()
+}
+
+fn element(html: &str, id: &str) -> Element {
+    Element::find(html, "id", id).unwrap()
+}
+
+#[test]
+fn button_can_be_disabled() {
+    let html = render(Button::primary("save", "Save").class("w-20"));
+    let button = element(&html, "save");
+
+    assert_eq!(button.text(), "Save");
+    assert_eq!(button.attr("type").as_deref(), Some("button"));
+    assert!(button.attr("class").unwrap().starts_with("w-20 "));
+    assert!(!button.is_disabled());
+
+    let html = render(Button::primary("save", "Save").disabled(true));
+    assert!(element(&html, "save").is_disabled());
+}
+
+#[test]
+fn invalid_input_is_flagged() {
+    let html = render(Input::text("name", "Ada"));
+    let input = element(&html, "name");
+
+    assert_eq!(input.value(), "Ada");
+    assert!(!input.is_invalid());
+    assert!(!input.attr("class").unwrap().contains("border-red-500"));
+
+    let html = render(Input::number("age", -1).invalid(true));
+    let input = element(&html, "age");
+
+    assert_eq!(input.attr("type").as_deref(), Some("number"));
+    assert!(input.is_invalid());
+    assert!(input.attr("class").unwrap().contains("border-red-500"));
+}
+
+#[test]
+fn input_states() {
+    let html = render(Input::date("when", "2022-10-01").disabled(true));
+    assert!(element(&html, "when").is_disabled());
+
+    let html = render(Input::text("count", 3).readonly());
+    assert!(element(&html, "count").has_attr("readonly"));
+
+    let html = render(Input::range("size", 4, 200, 30));
+    let input = element(&html, "size");
+    assert_eq!(input.attr("min").as_deref(), Some("4"));
+    assert_eq!(input.attr("max").as_deref(), Some("200"));
+    assert_eq!(input.value(), "30");
+
+    let html = render(Input::file("upload", ".csv"));
+    assert_eq!(
+        element(&html, "upload").attr("accept").as_deref(),
+        Some(".csv")
+    );
+}
+
+#[test]
+fn select_marks_the_selected_option() {
+    let html = render(
+        Select::new("fruit")
+            .option("apple", "Apple", false)
+            .option("pear", "Pear", true)
+            .size(2),
+    );
+    let select = element(&html, "fruit");
+
+    assert_eq!(select.value(), "pear");
+    assert_eq!(select.attr("size").as_deref(), Some("2"));
+    assert_eq!(select.options().len(), 2);
+}
+
+#[test]
+fn field_labels_its_control_and_shows_errors() {
+    let html = render(Field::new("Name", Input::text("name", "")));
+    let label = Element::find(&html, "for", "name").unwrap();
+
+    assert_eq!(label.text(), "Name");
+    assert!(Element::find(&html, "role", "alert").is_none());
+
+    let html =
+        render(Field::new("Name", Input::text("name", "").invalid(true)).error(Some("Required")));
+    let error = Element::find(&html, "role", "alert").unwrap();
+
+    assert_eq!(error.text(), "Required");
+    assert!(html.contains(&render(ErrorMessage::new("Required"))));
+}
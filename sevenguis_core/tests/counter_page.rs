@@ -0,0 +1,45 @@
+mod support;
+
+use sevenguis_core::counter_page::CounterPage;
+use sevenguis_core::counter_page::Id;
+use sevenguis_core::counter_page::Msg;
+use support::Harness;
+
+#[test]
+fn starts_at_zero() {
+    let harness = Harness::new(CounterPage {});
+
+    assert_eq!(harness.model().count, 0);
+    assert_eq!(harness.element(Id::Count).value(), "0");
+    assert!(harness.element(Id::Count).has_attr("readonly"));
+}
+
+#[test]
+fn increments_and_decrements() {
+    let mut harness = Harness::new(CounterPage {});
+
+    harness.send_all([Msg::Increment, Msg::Increment, Msg::Increment]);
+    assert_eq!(harness.element(Id::Count).value(), "3");
+
+    harness.send(Msg::Decrement);
+    harness.assert_no_effects();
+    assert_eq!(harness.element(Id::Count).value(), "2");
+}
+
+#[test]
+fn undo_and_redo() {
+    let mut harness = Harness::new(CounterPage {});
+
+    assert!(harness.element(Id::Undo).is_disabled());
+    assert!(harness.element(Id::Redo).is_disabled());
+
+    harness.send_all([Msg::Increment, Msg::Increment, Msg::Undo]);
+
+    assert_eq!(harness.model().count, 1);
+    assert!(!harness.element(Id::Undo).is_disabled());
+    assert!(!harness.element(Id::Redo).is_disabled());
+
+    harness.send(Msg::Redo);
+    assert_eq!(harness.model().count, 2);
+    assert!(harness.element(Id::Redo).is_disabled());
+}
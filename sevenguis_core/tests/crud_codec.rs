@@ -0,0 +1,290 @@
+use sevenguis_core::crud_codec;
+use sevenguis_core::crud_codec::Decoded;
+use sevenguis_core::crud_codec::Format;
+use sevenguis_core::crud_codec::ImportMode;
+use sevenguis_core::crud_codec::Record;
+use sevenguis_core::crud_codec::RowError;
+use sevenguis_core::crud_page::Person;
+
+fn person(id: u32, first_name: &str, last_name: &str) -> Person {
+    Person {
+        id,
+        first_name: first_name.to_string(),
+        last_name: last_name.to_string(),
+    }
+}
+
+fn record(id: Option<u32>, first_name: &str, last_name: &str) -> Record {
+    Record {
+        id,
+        first_name: first_name.to_string(),
+        last_name: last_name.to_string(),
+    }
+}
+
+fn decoded(records: Vec<Record>) -> Decoded {
+    Decoded {
+        records: records
+            .into_iter()
+            .enumerate()
+            .map(|(index, record)| (index + 2, record))
+            .collect(),
+        errors: vec![],
+    }
+}
+
+// Person has no PartialEq, it's compared by its fields
+fn fields(people: &[Person]) -> Vec<(u32, &str, &str)> {
+    people
+        .iter()
+        .map(|p| (p.id, p.first_name.as_str(), p.last_name.as_str()))
+        .collect()
+}
+
+fn records(decoded: &Decoded) -> Vec<(usize, Record)> {
+    decoded.records.clone()
+}
+
+#[test]
+fn csv_fields_are_quoted_when_needed() {
+    let people = [
+        person(1, "Ada", "Lovelace"),
+        person(2, "Jr, Sammy", "Davis"),
+        person(3, "Dwayne \"The Rock\"", "Johnson"),
+    ];
+
+    assert_eq!(
+        crud_codec::encode(&people, Format::Csv),
+        "id,first_name,last_name\n1,Ada,Lovelace\n2,\"Jr, Sammy\",Davis\n3,\"Dwayne \"\"The Rock\"\"\",Johnson\n"
+    );
+}
+
+#[test]
+fn csv_quotes_escapes_and_line_endings_are_decoded() {
+    let csv = "Last_Name, first_name ,id\r\n\"Davis\",\"Jr, Sammy\",2\r\nJohnson,\"Dwayne \"\"The Rock\"\"\",\r\n\r\n\"Hop\nper\",Grace,7";
+
+    let decoded = crud_codec::decode(csv, Format::Csv).unwrap();
+
+    assert!(decoded.errors.is_empty());
+    assert_eq!(
+        records(&decoded),
+        [
+            (2, record(Some(2), "Jr, Sammy", "Davis")),
+            (3, record(None, "Dwayne \"The Rock\"", "Johnson")),
+            (5, record(Some(7), "Grace", "Hop\nper")),
+        ]
+    );
+}
+
+#[test]
+fn csv_without_an_id_column_is_accepted() {
+    let decoded = crud_codec::decode("first_name,last_name\nAda,Lovelace\n", Format::Csv).unwrap();
+
+    assert_eq!(records(&decoded), [(2, record(None, "Ada", "Lovelace"))]);
+}
+
+#[test]
+fn invalid_csv_rows_are_reported_by_line() {
+    let csv = "id,first_name,last_name\nx,Ada,Lovelace\n3,,Nobody\n4,Grace\n5,Alan,Turing\n";
+
+    let decoded = crud_codec::decode(csv, Format::Csv).unwrap();
+
+    assert_eq!(records(&decoded), [(5, record(Some(5), "Alan", "Turing"))]);
+    assert_eq!(
+        decoded
+            .errors
+            .iter()
+            .map(|err| err.row)
+            .collect::<Vec<usize>>(),
+        [2, 3, 4]
+    );
+    assert_eq!(decoded.errors[0].to_string(), "Row 2: Invalid id: x");
+}
+
+#[test]
+fn unreadable_files_fail_as_a_whole() {
+    let cases = [
+        ("", Format::Csv),
+        ("id,name\n1,Ada\n", Format::Csv),
+        ("first_name,last_name\n\"Ada,Lovelace\n", Format::Csv),
+        ("{}", Format::Json),
+        ("not json", Format::Json),
+    ];
+
+    for (content, format) in cases {
+        assert!(crud_codec::decode(content, format).is_err(), "{}", content);
+    }
+}
+
+#[test]
+fn json_round_trips() {
+    let people = [
+        person(1, "Ada", "Lovelace"),
+        person(9, "Jr, Sammy", "Davis"),
+    ];
+
+    let json = crud_codec::encode(&people, Format::Json);
+    let decoded = crud_codec::decode(&json, Format::Json).unwrap();
+
+    assert!(decoded.errors.is_empty());
+    assert_eq!(
+        records(&decoded),
+        [
+            (1, record(Some(1), "Ada", "Lovelace")),
+            (2, record(Some(9), "Jr, Sammy", "Davis")),
+        ]
+    );
+}
+
+#[test]
+fn invalid_json_entries_are_reported_by_index() {
+    let json = r#"[{"first_name":"Ada","last_name":"Lovelace"},{"first_name":"Grace"},{"first_name":" ","last_name":"Hopper"}]"#;
+
+    let decoded = crud_codec::decode(json, Format::Json).unwrap();
+
+    assert_eq!(records(&decoded), [(1, record(None, "Ada", "Lovelace"))]);
+    assert_eq!(decoded.errors.len(), 2);
+    assert_eq!(decoded.errors[0].row, 2);
+    assert_eq!(decoded.errors[1].row, 3);
+}
+
+#[test]
+fn merge_skips_duplicates_and_gives_fresh_ids() {
+    let people = [person(1, "Ada", "Lovelace"), person(2, "Alan", "Turing")];
+    let file = decoded(vec![
+        record(Some(1), "Grace", "Hopper"),
+        record(None, "Ada", "Lovelace"),
+        record(Some(40), "Edsger", "Dijkstra"),
+    ]);
+
+    let imported = crud_codec::apply(&people, 3, file, ImportMode::Merge);
+
+    assert_eq!(
+        fields(&imported.people),
+        [
+            (1, "Ada", "Lovelace"),
+            (2, "Alan", "Turing"),
+            (3, "Grace", "Hopper"),
+            (4, "Edsger", "Dijkstra"),
+        ]
+    );
+    assert_eq!(imported.imported, 2);
+    assert_eq!(imported.next_id, 5);
+}
+
+#[test]
+fn merge_skips_duplicates_within_the_file() {
+    let people = [person(1, "Ada", "Lovelace")];
+    let file = decoded(vec![
+        record(None, "Grace", "Hopper"),
+        record(Some(7), "Grace", "Hopper"),
+        record(None, "Ada", "Lovelace"),
+        record(None, "Grace", "Hopper"),
+    ]);
+
+    let imported = crud_codec::apply(&people, 2, file, ImportMode::Merge);
+
+    assert_eq!(
+        fields(&imported.people),
+        [(1, "Ada", "Lovelace"), (2, "Grace", "Hopper")]
+    );
+    assert_eq!(imported.imported, 1);
+    assert_eq!(imported.next_id, 3);
+}
+
+#[test]
+fn merge_does_not_reuse_the_ids_of_deleted_people() {
+    let people = [person(1, "Ada", "Lovelace")];
+    let file = decoded(vec![record(None, "Grace", "Hopper")]);
+
+    let imported = crud_codec::apply(&people, 10, file, ImportMode::Merge);
+
+    assert_eq!(
+        fields(&imported.people),
+        [(1, "Ada", "Lovelace"), (10, "Grace", "Hopper")]
+    );
+    assert_eq!(imported.next_id, 11);
+}
+
+#[test]
+fn replace_keeps_ids_from_the_file() {
+    let people = [person(1, "Ada", "Lovelace")];
+    let file = decoded(vec![
+        record(Some(7), "Grace", "Hopper"),
+        record(Some(7), "Alan", "Turing"),
+        record(None, "Edsger", "Dijkstra"),
+        record(Some(0), "Barbara", "Liskov"),
+        record(Some(3), "Ada", "Lovelace"),
+    ]);
+
+    let imported = crud_codec::apply(&people, 10, file, ImportMode::Replace);
+
+    assert_eq!(
+        fields(&imported.people),
+        [
+            (7, "Grace", "Hopper"),
+            (3, "Ada", "Lovelace"),
+            (8, "Alan", "Turing"),
+            (9, "Edsger", "Dijkstra"),
+            (10, "Barbara", "Liskov"),
+        ]
+    );
+    assert_eq!(imported.imported, 5);
+    assert_eq!(imported.next_id, 11);
+}
+
+#[test]
+fn huge_ids_never_overflow() {
+    let file = decoded(vec![
+        record(Some(u32::MAX), "Ada", "Lovelace"),
+        record(Some(u32::MAX - 1), "Grace", "Hopper"),
+        record(None, "Alan", "Turing"),
+    ]);
+
+    let imported = crud_codec::apply(&[], 1, file, ImportMode::Replace);
+
+    assert_eq!(
+        fields(&imported.people),
+        [(u32::MAX - 1, "Grace", "Hopper")]
+    );
+    assert_eq!(imported.next_id, u32::MAX);
+    assert_eq!(
+        imported.errors,
+        [
+            RowError {
+                row: 2,
+                message: "No ids left for new people".to_string(),
+            },
+            RowError {
+                row: 4,
+                message: "No ids left for new people".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn merge_reports_rows_once_ids_run_out() {
+    let people = [person(1, "Ada", "Lovelace")];
+    let file = decoded(vec![
+        record(None, "Grace", "Hopper"),
+        record(None, "Alan", "Turing"),
+    ]);
+
+    let imported = crud_codec::apply(&people, u32::MAX - 1, file, ImportMode::Merge);
+
+    assert_eq!(
+        fields(&imported.people),
+        [(1, "Ada", "Lovelace"), (u32::MAX - 1, "Grace", "Hopper")]
+    );
+    assert_eq!(imported.imported, 1);
+    assert_eq!(imported.next_id, u32::MAX);
+    assert_eq!(
+        imported
+            .errors
+            .iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<String>>(),
+        ["Row 3: No ids left for new people"]
+    );
+}
@@ -0,0 +1,480 @@
+mod support;
+
+use polyester::browser;
+use sevenguis_core::crud_codec;
+use sevenguis_core::crud_codec::Format;
+use sevenguis_core::crud_codec::ImportMode;
+use sevenguis_core::crud_page::AppEffect;
+use sevenguis_core::crud_page::CrudPage;
+use sevenguis_core::crud_page::Id;
+use sevenguis_core::crud_page::ImportedFile;
+use sevenguis_core::crud_page::Model;
+use sevenguis_core::crud_page::Msg;
+use sevenguis_core::crud_page::StoredPeople;
+use sevenguis_core::history::Undoable;
+use sevenguis_core::page_error::ErrorKind;
+use sevenguis_core::page_error::PageError;
+use sevenguis_core::runtime;
+use support::value;
+use support::Harness;
+
+fn crud() -> Harness<CrudPage, Model, Msg, AppEffect> {
+    let mut harness = Harness::new(CrudPage {});
+    harness.send(Msg::PeopleLoaded(None));
+    harness
+}
+
+fn names(harness: &Harness<CrudPage, Model, Msg, AppEffect>) -> Vec<String> {
+    harness
+        .element(Id::People)
+        .options()
+        .iter()
+        .map(|option| option.text())
+        .collect()
+}
+
+fn saved(model: &Model) -> browser::Effect<Msg, AppEffect> {
+    let stored = StoredPeople::new(model.next_id, model.people.clone());
+    browser::app_effect(AppEffect::SavePeople(stored))
+}
+
+#[test]
+fn loads_people_on_start() {
+    let harness = Harness::new(CrudPage {});
+
+    harness.assert_effects(vec![browser::app_effect(AppEffect::LoadPeople)]);
+}
+
+#[test]
+fn falls_back_to_seed_data() {
+    let mut harness = Harness::new(CrudPage {});
+
+    harness.send(Msg::PeopleLoaded(Some("not json".to_string())));
+
+    harness.assert_ok();
+    assert_eq!(
+        names(&harness),
+        ["Emil, Hans", "Mustermann, Max", "Tisch, Roman"]
+    );
+}
+
+#[test]
+fn restores_stored_people() {
+    let mut harness = Harness::new(CrudPage {});
+    let stored =
+        r#"{"version":1,"nextId":8,"people":[{"id":7,"first_name":"Ada","last_name":"Lovelace"}]}"#;
+
+    harness.send(Msg::PeopleLoaded(Some(stored.to_string())));
+
+    assert_eq!(names(&harness), ["Lovelace, Ada"]);
+    assert_eq!(harness.element(Id::FirstName).value(), "Ada");
+    assert_eq!(harness.model().next_id, 8);
+}
+
+#[test]
+fn stored_ids_without_a_successor_fall_back_to_seed_data() {
+    let mut harness = Harness::new(CrudPage {});
+    let stored = r#"{"version":1,"nextId":8,"people":[{"id":4294967295,"first_name":"Ada","last_name":"Lovelace"}]}"#;
+
+    assert!(StoredPeople::decode(stored).is_err());
+
+    harness.send(Msg::PeopleLoaded(Some(stored.to_string())));
+
+    harness.assert_ok();
+    assert_eq!(
+        names(&harness),
+        ["Emil, Hans", "Mustermann, Max", "Tisch, Roman"]
+    );
+    assert_eq!(harness.model().next_id, 4);
+}
+
+#[test]
+fn create_fails_once_ids_run_out() {
+    let mut harness = Harness::new(CrudPage {});
+    let stored = r#"{"version":1,"nextId":4294967295,"people":[{"id":7,"first_name":"Ada","last_name":"Lovelace"}]}"#;
+
+    harness.send(Msg::PeopleLoaded(Some(stored.to_string())));
+    harness.send_all([
+        Msg::FirstNameChanged("Grace".to_string()),
+        Msg::LastNameChanged("Hopper".to_string()),
+        Msg::Create,
+    ]);
+
+    let error = harness.assert_error();
+    assert_eq!(error.context, "create");
+    assert_eq!(names(&harness), ["Lovelace, Ada"]);
+}
+
+#[test]
+fn selecting_a_person_fills_the_form() {
+    let mut harness = crud();
+
+    harness.send(Msg::SelectedPersonChanged(value(2)));
+
+    assert_eq!(harness.element(Id::FirstName).value(), "Max");
+    assert_eq!(harness.element(Id::LastName).value(), "Mustermann");
+    assert_eq!(harness.element(Id::People).value(), "2");
+}
+
+#[test]
+fn filter_matches_surname_prefix() {
+    let mut harness = crud();
+
+    harness.send(Msg::FilterChanged("M".to_string()));
+
+    assert_eq!(names(&harness), ["Mustermann, Max"]);
+
+    harness.send(Msg::FilterChanged("x".to_string()));
+
+    assert!(names(&harness).is_empty());
+    assert!(harness.element(Id::Update).is_disabled());
+    assert!(harness.element(Id::Delete).is_disabled());
+}
+
+#[test]
+fn filtering_out_the_selection_selects_the_first_visible_person() {
+    let mut harness = crud();
+
+    harness.send(Msg::FilterChanged("M".to_string()));
+
+    assert_eq!(harness.model().selected_id, 2);
+    assert_eq!(harness.element(Id::FirstName).value(), "Max");
+
+    harness.send_all([Msg::FirstNameChanged("Moritz".to_string()), Msg::Update]);
+
+    harness.assert_ok();
+    harness.send(Msg::FilterChanged("".to_string()));
+    assert_eq!(
+        names(&harness),
+        ["Emil, Hans", "Mustermann, Moritz", "Tisch, Roman"]
+    );
+}
+
+#[test]
+fn filtering_out_everyone_clears_the_selection() {
+    let mut harness = crud();
+
+    harness.send(Msg::FilterChanged("x".to_string()));
+
+    assert_eq!(harness.model().selected_id, 0);
+    assert_eq!(harness.element(Id::FirstName).value(), "");
+    assert_eq!(harness.element(Id::LastName).value(), "");
+}
+
+#[test]
+fn filtering_keeps_a_visible_selection() {
+    let mut harness = crud();
+
+    harness.send_all([
+        Msg::SelectedPersonChanged(value(3)),
+        Msg::FilterChanged("t".to_string()),
+    ]);
+
+    assert_eq!(harness.model().selected_id, 3);
+    assert_eq!(harness.element(Id::FirstName).value(), "Roman");
+}
+
+#[test]
+fn create_adds_and_selects_a_person() {
+    let mut harness = crud();
+
+    harness.send_all([
+        Msg::FirstNameChanged("Ada".to_string()),
+        Msg::LastNameChanged("Lovelace".to_string()),
+        Msg::Create,
+    ]);
+
+    harness.assert_ok();
+    harness.assert_effects(vec![saved(harness.model())]);
+    assert_eq!(names(&harness).len(), 4);
+    assert_eq!(harness.element(Id::People).value(), "4");
+}
+
+#[test]
+fn update_changes_the_selected_person() {
+    let mut harness = crud();
+
+    harness.send_all([
+        Msg::SelectedPersonChanged(value(3)),
+        Msg::FirstNameChanged("Rosa".to_string()),
+        Msg::Update,
+    ]);
+
+    harness.assert_effects(vec![saved(harness.model())]);
+    assert_eq!(
+        names(&harness),
+        ["Emil, Hans", "Mustermann, Max", "Tisch, Rosa"]
+    );
+}
+
+#[test]
+fn updating_the_selection_out_of_the_filter_selects_the_first_visible_person() {
+    let mut harness = crud();
+
+    harness.send_all([
+        Msg::SelectedPersonChanged(value(1)),
+        Msg::LastNameChanged("Tamm".to_string()),
+        Msg::Update,
+        Msg::FilterChanged("t".to_string()),
+    ]);
+
+    assert_eq!(names(&harness), ["Tamm, Hans", "Tisch, Roman"]);
+    assert_eq!(harness.model().selected_id, 1);
+
+    harness.send_all([Msg::LastNameChanged("Weber".to_string()), Msg::Update]);
+
+    harness.assert_ok();
+    assert_eq!(names(&harness), ["Tisch, Roman"]);
+    assert_eq!(harness.model().selected_id, 3);
+    assert_eq!(harness.element(Id::FirstName).value(), "Roman");
+    assert_eq!(harness.element(Id::LastName).value(), "Tisch");
+}
+
+#[test]
+fn updating_the_last_visible_person_out_of_the_filter_clears_the_selection() {
+    let mut harness = crud();
+
+    harness.send_all([
+        Msg::FilterChanged("m".to_string()),
+        Msg::LastNameChanged("Weber".to_string()),
+        Msg::Update,
+    ]);
+
+    harness.assert_ok();
+    assert!(names(&harness).is_empty());
+    assert_eq!(harness.model().selected_id, 0);
+    assert_eq!(harness.element(Id::FirstName).value(), "");
+    assert_eq!(harness.element(Id::LastName).value(), "");
+    assert!(harness.element(Id::Update).is_disabled());
+}
+
+#[test]
+fn delete_removes_the_selected_person() {
+    let mut harness = crud();
+
+    harness.send_all([Msg::SelectedPersonChanged(value(2)), Msg::Delete]);
+
+    harness.assert_effects(vec![saved(harness.model())]);
+    assert_eq!(names(&harness), ["Emil, Hans", "Tisch, Roman"]);
+}
+
+#[test]
+fn delete_without_a_visible_selection_fails() {
+    let mut harness = crud();
+
+    harness.send_all([Msg::FilterChanged("x".to_string()), Msg::Delete]);
+
+    let error = harness.assert_error();
+    assert_eq!(error.kind, ErrorKind::InvalidState);
+    assert_eq!(error.context, "delete");
+    assert_eq!(harness.model().people.len(), 3);
+}
+
+#[test]
+fn failed_update_leaves_the_model_untouched() {
+    let mut harness = crud();
+
+    harness.send_all([
+        Msg::FirstNameChanged("Ada".to_string()),
+        Msg::FilterChanged("x".to_string()),
+    ]);
+    let before = serde_json::to_value(harness.model()).unwrap();
+
+    harness.send(Msg::Update);
+
+    assert_eq!(harness.assert_error().context, "update");
+
+    let mut after = serde_json::to_value(harness.model()).unwrap();
+    after["error"] = serde_json::Value::Null;
+    assert_eq!(after, before);
+    assert!(!harness.model().history.can_undo());
+}
+
+#[test]
+fn changes_before_a_failure_are_rolled_back() {
+    let harness = crud();
+    let mut model = harness.model().clone();
+    let before = serde_json::to_value(&model).unwrap();
+
+    let result = runtime::update::<Model, Msg, AppEffect>(&mut model, |model| {
+        model.record();
+        model.people.clear();
+        model.next_id = 99;
+        model.filter = "x".to_string();
+        Err(PageError::invalid_state("update", "nope"))
+    });
+
+    assert!(result.unwrap().is_empty());
+    assert_eq!(
+        model.error,
+        Some(PageError::invalid_state("update", "nope"))
+    );
+
+    let mut after = serde_json::to_value(&model).unwrap();
+    after["error"] = serde_json::Value::Null;
+    assert_eq!(after, before);
+    assert!(!model.history.can_undo());
+}
+
+#[test]
+fn invalid_names_are_reported_inline() {
+    let mut harness = crud();
+
+    harness.send(Msg::FirstNameChanged("  ".to_string()));
+
+    assert!(harness.element(Id::Create).is_disabled());
+    assert!(harness.element(Id::Update).is_disabled());
+    assert!(harness.element(Id::FirstName).is_invalid());
+
+    harness.send(Msg::Create);
+    assert_eq!(harness.assert_error().context, "create");
+}
+
+#[test]
+fn create_warns_about_copying_the_selected_person() {
+    let mut harness = crud();
+
+    harness.send(Msg::SelectedPersonChanged(value(2)));
+
+    assert!(harness.contains_text("Create would add a second person with this name"));
+    assert!(!harness.contains_text("A person with this name already exists"));
+
+    harness.send(Msg::FirstNameChanged("Maxine".to_string()));
+
+    assert!(!harness.contains_text("Create would add a second person with this name"));
+}
+
+#[test]
+fn update_warns_about_copying_someone_else() {
+    let mut harness = crud();
+
+    harness.send_all([
+        Msg::SelectedPersonChanged(value(2)),
+        Msg::FirstNameChanged("Hans".to_string()),
+        Msg::LastNameChanged("Emil".to_string()),
+    ]);
+
+    assert!(harness.contains_text("A person with this name already exists"));
+}
+
+#[test]
+fn create_without_a_selection_warns_about_duplicates() {
+    let mut harness = crud();
+
+    harness.send_all([
+        Msg::FilterChanged("x".to_string()),
+        Msg::FirstNameChanged("Roman".to_string()),
+        Msg::LastNameChanged("Tisch".to_string()),
+    ]);
+
+    assert!(harness.contains_text("Create would add a second person with this name"));
+}
+
+#[test]
+fn discard_restores_the_selected_person() {
+    let mut harness = crud();
+
+    harness.send(Msg::FirstNameChanged("Hannes".to_string()));
+    assert!(harness.contains_text("Unsaved changes"));
+
+    harness.send(Msg::Discard);
+    assert_eq!(harness.element(Id::FirstName).value(), "Hans");
+    assert!(harness.element(Id::Discard).is_disabled());
+}
+
+#[test]
+fn undo_and_redo_a_delete() {
+    let mut harness = crud();
+
+    harness.send_all([Msg::Delete, Msg::Undo]);
+    assert_eq!(harness.model().people.len(), 3);
+
+    harness.send(Msg::Redo);
+    assert_eq!(harness.model().people.len(), 2);
+}
+
+#[test]
+fn export_downloads_a_file() {
+    let mut harness = crud();
+
+    harness.send(Msg::Export(Format::Csv));
+
+    harness.assert_effects(vec![browser::app_effect(AppEffect::Download {
+        filename: "people.csv".to_string(),
+        mime_type: "text/csv".to_string(),
+        content: "id,first_name,last_name\n1,Hans,Emil\n2,Max,Mustermann\n3,Roman,Tisch\n"
+            .to_string(),
+    })]);
+}
+
+#[test]
+fn import_reports_invalid_rows() {
+    let mut harness = crud();
+
+    harness.send(Msg::FileImported(ImportedFile {
+        name: "people.csv".to_string(),
+        content: "first_name,last_name\nAda,Lovelace\n,Nobody\n".to_string(),
+    }));
+
+    harness.assert_ok();
+    assert_eq!(harness.model().people.len(), 4);
+    assert!(harness.contains_text("Imported 1 people"));
+    assert!(harness.contains_text("Row 3: First name"));
+}
+
+#[test]
+fn exported_people_import_back() {
+    for format in [Format::Csv, Format::Json] {
+        let mut harness = crud();
+
+        harness.send_all([
+            Msg::SelectedPersonChanged(value(2)),
+            Msg::FirstNameChanged("Max, \"Maxi\"".to_string()),
+            Msg::Update,
+            Msg::Export(format),
+        ]);
+
+        let exported = serde_json::to_value(&harness.model().people).unwrap();
+        let content = crud_codec::encode(&harness.model().people, format);
+        let filename = format!("people.{}", format.extension());
+
+        harness.assert_effects(vec![browser::app_effect(AppEffect::Download {
+            filename: filename.clone(),
+            mime_type: format.mime_type().to_string(),
+            content: content.clone(),
+        })]);
+
+        harness.send_all([
+            Msg::Delete,
+            Msg::Delete,
+            Msg::ImportModeChanged(value(ImportMode::Replace)),
+            Msg::FileImported(ImportedFile {
+                name: filename,
+                content,
+            }),
+        ]);
+
+        harness.assert_ok();
+        assert_eq!(
+            serde_json::to_value(&harness.model().people).unwrap(),
+            exported
+        );
+        assert_eq!(harness.model().next_id, 4);
+        assert!(harness.contains_text("Imported 3 people"));
+    }
+}
+
+#[test]
+fn merging_an_export_adds_nobody() {
+    let mut harness = crud();
+    let content = crud_codec::encode(&harness.model().people, Format::Csv);
+
+    harness.send(Msg::FileImported(ImportedFile {
+        name: "people.csv".to_string(),
+        content,
+    }));
+
+    harness.assert_ok();
+    assert_eq!(harness.model().people.len(), 3);
+    assert!(harness.contains_text("Imported 0 people"));
+}
@@ -0,0 +1,92 @@
+use sevenguis_core::duration;
+use std::time::Duration;
+
+#[test]
+fn parses_units_and_clocks() {
+    let cases = [
+        ("1m30s", 90_000),
+        ("90s", 90_000),
+        ("90", 90_000),
+        ("00:01:30", 90_000),
+        ("1:30", 90_000),
+        ("1h 2m 3s", 3_723_000),
+        ("1.5m", 90_000),
+        ("2.25s", 2250),
+        ("250ms", 250),
+        ("90:00", 5_400_000),
+        ("  15s ", 15_000),
+    ];
+
+    for (text, millis) in cases {
+        assert_eq!(
+            duration::parse(text),
+            Ok(Duration::from_millis(millis)),
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+fn rejects_what_isnt_a_duration() {
+    for text in [
+        "",
+        "soon",
+        "1x",
+        "m",
+        "1m30",
+        "-5s",
+        "00:60",
+        "1:60:00",
+        "1:2:3:4",
+        "99999999999999999:00:00",
+        "99999999999999999999h1s",
+        "5124095576030h 1h",
+        "18446744073709551615",
+        &"9".repeat(400),
+    ] {
+        assert!(duration::parse(text).is_err(), "{}", text);
+    }
+
+    assert_eq!(
+        duration::parse("soon").unwrap_err(),
+        "soon isn't a duration, try 1m30s, 90s or 00:01:30"
+    );
+}
+
+#[test]
+fn formats_input_that_parses_back() {
+    let cases = [
+        (0, "0s"),
+        (15_000, "15s"),
+        (90_000, "1m30s"),
+        (3_600_000, "1h"),
+        (3_723_500, "1h2m3.5s"),
+        (15_347, "15.347s"),
+    ];
+
+    for (millis, text) in cases {
+        let duration = Duration::from_millis(millis);
+
+        assert_eq!(duration::format_input(duration), text);
+        assert_eq!(duration::parse(text), Ok(duration));
+    }
+}
+
+#[test]
+fn longer_durations_display_as_a_clock() {
+    let cases = [
+        (4200, "4.20s"),
+        (59_990, "59.99s"),
+        (90_000, "01:30"),
+        (3_599_000, "59:59"),
+        (3_723_000, "01:02:03"),
+    ];
+
+    for (millis, text) in cases {
+        assert_eq!(
+            duration::format_display(Duration::from_millis(millis)),
+            text
+        );
+    }
+}
@@ -0,0 +1,128 @@
+mod support;
+
+use polyester::time::Posix;
+use sevenguis_core::clock::ManualClock;
+use sevenguis_core::flight_page::AppEffect;
+use sevenguis_core::flight_page::FlightPage;
+use sevenguis_core::flight_page::FlightType;
+use sevenguis_core::flight_page::Id;
+use sevenguis_core::flight_page::Model;
+use sevenguis_core::flight_page::Msg;
+use sevenguis_core::page_error;
+use std::rc::Rc;
+use support::value;
+use support::Harness;
+
+// 2022-10-01T00:00:00Z
+const TODAY: i128 = 1_664_582_400_000;
+
+fn booker() -> Harness<FlightPage, Model, Msg, AppEffect> {
+    Harness::new(FlightPage {
+        clock: Rc::new(ManualClock::new(Posix::from_millis(TODAY))),
+    })
+}
+
+fn return_flight() -> Msg {
+    Msg::FlightTypeChanged(value(FlightType::Return))
+}
+
+#[test]
+fn starts_as_a_one_way_flight_today() {
+    let harness = booker();
+
+    let flight_type = harness.element(Id::FlightType).selected_option().unwrap();
+    assert_eq!(flight_type.text(), "One-way flight");
+    assert_eq!(harness.element(Id::StartDate).value(), "2022-10-01");
+    assert_eq!(harness.element(Id::ReturnDate).value(), "2022-10-01");
+    assert!(harness.element(Id::ReturnDate).is_disabled());
+    assert!(!harness.element(Id::Book).is_disabled());
+}
+
+#[test]
+fn return_date_is_enabled_for_return_flights() {
+    let mut harness = booker();
+
+    harness.send(return_flight());
+
+    harness.assert_ok();
+    assert!(!harness.element(Id::ReturnDate).is_disabled());
+}
+
+#[test]
+fn return_date_before_start_date_cannot_be_booked() {
+    let mut harness = booker();
+
+    harness.send_all([
+        return_flight(),
+        Msg::StartDateChanged("2022-10-10".to_string()),
+        Msg::ReturnDateChanged("2022-10-05".to_string()),
+    ]);
+
+    assert!(harness.element(Id::Book).is_disabled());
+    assert!(harness.element(Id::ReturnDate).is_invalid());
+
+    harness.send(Msg::FlightTypeChanged(value(FlightType::OneWay)));
+    assert!(!harness.element(Id::Book).is_disabled());
+}
+
+#[test]
+fn start_date_in_the_past_cannot_be_booked() {
+    let mut harness = booker();
+
+    harness.send(Msg::StartDateChanged("2022-09-30".to_string()));
+
+    assert!(harness.element(Id::Book).is_disabled());
+    assert!(harness.element(Id::StartDate).is_invalid());
+}
+
+#[test]
+fn malformed_date_is_kept_and_flagged() {
+    let mut harness = booker();
+
+    harness.send(Msg::StartDateChanged("2022-13-01".to_string()));
+
+    assert_eq!(harness.element(Id::StartDate).value(), "2022-13-01");
+    assert!(harness.element(Id::Book).is_disabled());
+
+    harness.send(Msg::BookFlight);
+    assert_eq!(harness.assert_error().context, "book");
+    assert!(harness.exists(page_error::Id::PageError));
+}
+
+#[test]
+fn booking_a_one_way_flight() {
+    let mut harness = booker();
+
+    harness.send_all([
+        Msg::StartDateChanged("2022-10-24".to_string()),
+        Msg::BookFlight,
+    ]);
+
+    harness.assert_ok();
+    assert!(harness.contains_text("You have booked a one-way flight on 24.10.2022."));
+    assert!(!harness.exists(Id::Book));
+}
+
+#[test]
+fn booking_a_return_flight() {
+    let mut harness = booker();
+
+    harness.send_all([
+        return_flight(),
+        Msg::StartDateChanged("2022-10-24".to_string()),
+        Msg::ReturnDateChanged("2022-10-31".to_string()),
+        Msg::BookFlight,
+    ]);
+
+    assert!(harness.contains_text("You have booked a return flight from 24.10.2022 to 31.10.2022."));
+}
+
+#[test]
+fn reset_after_booking_starts_a_new_draft() {
+    let mut harness = booker();
+
+    harness.send_all([Msg::BookFlight, Msg::Reset]);
+
+    assert!(harness.exists(Id::Book));
+    assert_eq!(harness.element(Id::StartDate).value(), "2022-10-01");
+}
@@ -0,0 +1,102 @@
+use sevenguis_core::history;
+use sevenguis_core::history::History;
+
+// Undoes everything, returning the snapshots from newest to oldest
+fn undo_all(history: &mut History<usize>, mut current: usize) -> Vec<usize> {
+    let mut snapshots = vec![];
+
+    while let Some(previous) = history.undo(current) {
+        snapshots.push(previous);
+        current = previous;
+    }
+
+    snapshots
+}
+
+#[test]
+fn oldest_entries_are_dropped_at_the_limit() {
+    let mut history = History::default();
+    let recorded = history::DEFAULT_LIMIT + 5;
+
+    for snapshot in 0..recorded {
+        history.record(snapshot);
+    }
+
+    let snapshots = undo_all(&mut history, recorded);
+
+    assert_eq!(snapshots.len(), history::DEFAULT_LIMIT);
+    assert_eq!(snapshots.first(), Some(&(recorded - 1)));
+    assert_eq!(snapshots.last(), Some(&5));
+    assert!(!history.can_undo());
+}
+
+#[test]
+fn undo_and_redo_swap_snapshots() {
+    let mut history = History::new(10);
+
+    history.record(1);
+    history.record(2);
+
+    assert_eq!(history.undo(3), Some(2));
+    assert_eq!(history.undo(2), Some(1));
+    assert!(history.can_redo());
+    assert_eq!(history.redo(1), Some(2));
+    assert_eq!(history.redo(2), Some(3));
+    assert!(!history.can_redo());
+}
+
+#[test]
+fn recording_clears_redo() {
+    let mut history = History::new(10);
+
+    history.record(1);
+    history.undo(2);
+    assert!(history.can_redo());
+
+    history.record(1);
+
+    assert!(!history.can_redo());
+    assert_eq!(history.redo(5), None);
+}
+
+#[test]
+fn empty_history_does_nothing() {
+    let mut history: History<usize> = History::new(10);
+
+    assert_eq!(history.undo(1), None);
+    assert_eq!(history.redo(1), None);
+    assert!(!history.can_undo());
+    assert!(!history.can_redo());
+}
+
+#[test]
+fn zero_limit_records_nothing() {
+    let mut history = History::new(0);
+
+    history.record(1);
+
+    assert!(!history.can_undo());
+    assert_eq!(history.undo(2), None);
+}
+
+#[test]
+fn stored_histories_are_clamped_to_their_limit() {
+    let json = r#"{"undoStack":[0,1,2,3,4],"redoStack":[9,8,7,6],"limit":3}"#;
+    let mut history: History<usize> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(history.redo(5), Some(6));
+    assert_eq!(history.redo(6), Some(7));
+    assert_eq!(history.redo(7), Some(8));
+    assert_eq!(history.redo(8), None);
+    assert_eq!(undo_all(&mut history, 8), [7, 6, 5]);
+}
+
+#[test]
+fn recording_trims_a_stored_history() {
+    let json = r#"{"undoStack":[0,1,2,3,4],"redoStack":[],"limit":3}"#;
+    let mut history: History<usize> = serde_json::from_str(json).unwrap();
+
+    history.record(5);
+
+    assert_eq!(undo_all(&mut history, 6), [5, 4, 3]);
+}
@@ -0,0 +1,22 @@
+mod support;
+
+use sevenguis_core::home_page::HomePage;
+use support::Harness;
+
+#[test]
+fn links_to_every_task() {
+    let harness = Harness::new(HomePage {});
+    let html = harness.html();
+
+    for route in [
+        "/counter/",
+        "/temperature/",
+        "/flight/",
+        "/timer/",
+        "/crud/",
+        "/circle/",
+        "/cells/",
+    ] {
+        assert!(html.contains(&format!("href=\"{}\"", route)), "{}", route);
+    }
+}
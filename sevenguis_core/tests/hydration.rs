@@ -0,0 +1,196 @@
+mod support;
+
+use polyester::page::Page;
+use polyester::time::Posix;
+use sevenguis_core::clock::ManualClock;
+use sevenguis_core::clock::SystemClock;
+use sevenguis_core::crud_page;
+use sevenguis_core::flight_page;
+use sevenguis_core::hydration;
+use sevenguis_core::hydration::Hydrated;
+use sevenguis_core::registry;
+use sevenguis_core::timer_page;
+use std::rc::Rc;
+use std::time::Duration;
+use support::value;
+use support::Element;
+use support::Harness;
+
+// 2022-10-01T00:00:00Z
+const TODAY: i128 = 1_664_582_400_000;
+
+fn flight_page() -> flight_page::FlightPage {
+    flight_page::FlightPage {
+        clock: Rc::new(ManualClock::new(Posix::from_millis(TODAY))),
+    }
+}
+
+// Renders the page from its model the way the cli does and reads the embedded model back
+fn embedded_model<Model, Msg, AppEffect>(
+    page: &impl Page<Model, Msg, AppEffect>,
+    model: &Model,
+) -> String
+where
+    Model: serde::Serialize,
+{
+    let markup = hydration::embed_model(page.view(model), model).unwrap();
+    let html = markup.to_markup().into_string();
+
+    Element::find(&html, "id", hydration::MODEL_ID)
+        .unwrap()
+        .inner_html
+}
+
+#[test]
+fn hydrates_from_the_embedded_model() {
+    let mut booker = Harness::new(flight_page());
+    booker.send_all([
+        flight_page::Msg::StartDateChanged("2022-10-24".to_string()),
+        flight_page::Msg::BookFlight,
+    ]);
+
+    let json = embedded_model(&flight_page(), booker.model());
+    let hydrated = Harness::new(Hydrated::new(flight_page(), Some(json)));
+
+    hydrated.assert_no_effects();
+    assert!(hydrated.contains_text("You have booked a one-way flight on 24.10.2022."));
+}
+
+#[test]
+fn hydrated_pages_keep_working() {
+    let mut crud = Harness::new(crud_page::CrudPage {});
+    crud.send_all([
+        crud_page::Msg::PeopleLoaded(None),
+        crud_page::Msg::FilterChanged("t".to_string()),
+        crud_page::Msg::SelectedPersonChanged(value(3)),
+    ]);
+
+    let json = embedded_model(&crud_page::CrudPage {}, crud.model());
+    let mut hydrated = Harness::new(Hydrated::new(crud_page::CrudPage {}, Some(json)));
+
+    // Without stored people the embedded ones stay
+    hydrated.assert_effects(vec![polyester::browser::app_effect(
+        crud_page::AppEffect::LoadPeople,
+    )]);
+    hydrated.send(crud_page::Msg::PeopleLoaded(None));
+    assert_eq!(hydrated.model().filter, "t");
+    assert_eq!(hydrated.element("first-name").value(), "Roman");
+
+    hydrated.send(crud_page::Msg::Delete);
+    hydrated.assert_ok();
+    assert_eq!(hydrated.model().people.len(), 2);
+}
+
+#[test]
+fn embedded_model_cannot_close_the_script_element() {
+    let mut crud = Harness::new(crud_page::CrudPage {});
+    crud.send_all([
+        crud_page::Msg::FirstNameChanged("</script>".to_string()),
+        crud_page::Msg::LastNameChanged("Tag".to_string()),
+        crud_page::Msg::Create,
+    ]);
+
+    let json = embedded_model(&crud_page::CrudPage {}, crud.model());
+    assert!(!json.contains("</script>"));
+
+    let model: crud_page::Model = hydration::decode_model(&json).unwrap();
+    assert!(model.people.iter().any(|p| p.first_name == "</script>"));
+}
+
+#[test]
+fn falls_back_to_init_without_a_usable_model() {
+    let without = Harness::new(Hydrated::new(crud_page::CrudPage {}, None));
+    let broken = Harness::new(Hydrated::new(crud_page::CrudPage {}, Some("{".to_string())));
+
+    for harness in [without, broken] {
+        assert_eq!(harness.model().people.len(), 3);
+        harness.assert_effects(vec![polyester::browser::app_effect(
+            crud_page::AppEffect::LoadPeople,
+        )]);
+    }
+}
+
+#[test]
+fn stored_data_is_loaded_over_the_embedded_model() {
+    let page = crud_page::CrudPage {};
+    let json = embedded_model(&page, &page.init().0);
+    let stored = crud_page::StoredPeople::new(
+        8,
+        vec![crud_page::Person {
+            id: 7,
+            first_name: "Ada".to_string(),
+            last_name: "Lovelace".to_string(),
+        }],
+    );
+
+    let mut hydrated = Harness::new(Hydrated::new(crud_page::CrudPage {}, Some(json)));
+    hydrated.send(crud_page::Msg::PeopleLoaded(Some(
+        serde_json::to_string(&stored).unwrap(),
+    )));
+
+    assert_eq!(hydrated.model().people.len(), 1);
+    assert_eq!(hydrated.model().next_id, 8);
+}
+
+#[test]
+fn inconsistent_models_fall_back_to_init() {
+    let page = crud_page::CrudPage {};
+    let (model, _) = page.init();
+
+    let mut duplicate = model.clone();
+    duplicate.people[1].id = duplicate.people[0].id;
+
+    let mut taken_next_id = model.clone();
+    taken_next_id.next_id = 3;
+
+    let mut missing_selection = model.clone();
+    missing_selection.selected_id = 9;
+
+    let mut hidden_selection = model;
+    hidden_selection.selected_id = 3;
+    hidden_selection.filter = "m".to_string();
+
+    for model in [
+        duplicate,
+        taken_next_id,
+        missing_selection,
+        hidden_selection,
+    ] {
+        let json = embedded_model(&page, &model);
+        assert!(hydration::decode_model::<crud_page::Model>(&json).is_err());
+
+        let hydrated = Harness::new(Hydrated::new(crud_page::CrudPage {}, Some(json)));
+        assert_eq!(hydrated.model().next_id, 4);
+        assert_eq!(hydrated.model().selected_id, 1);
+        assert_eq!(hydrated.model().filter, "");
+    }
+}
+
+#[test]
+fn timer_models_need_a_slider_range() {
+    let page = timer_page::TimerPage {
+        clock: Rc::new(ManualClock::new(Posix::from_millis(TODAY))),
+    };
+    let (mut model, _) = page.init();
+    model.slider_max = Duration::ZERO;
+
+    let json = embedded_model(&page, &model);
+    let hydrated = Harness::new(Hydrated::new(page, Some(json)));
+
+    assert!(!hydrated.model().slider_max.is_zero());
+    hydrated.assert_effects(vec![polyester::browser::app_effect(
+        timer_page::AppEffect::LoadTimers,
+    )]);
+}
+
+#[test]
+fn rendering_an_inconsistent_model_fails() {
+    let page = crud_page::CrudPage {};
+    let mut model = page.init().0;
+    model.next_id = 1;
+
+    let json = embedded_model(&page, &model);
+    let render = (registry::CRUD.new)(Rc::new(SystemClock)).render(Some(&json));
+
+    assert!(render.unwrap_err().starts_with("Invalid model"));
+}
@@ -0,0 +1,81 @@
+use polyester::time::Posix;
+use sevenguis_core::clock::ManualClock;
+use sevenguis_core::layout;
+use sevenguis_core::registry;
+use std::rc::Rc;
+
+// 2022-10-01T00:00:00Z
+const NOW: i128 = 1_664_582_400_000;
+
+fn render(page: &registry::PageInfo) -> String {
+    (page.new)(Rc::new(ManualClock::new(Posix::from_millis(NOW))))
+        .render(None)
+        .unwrap()
+}
+
+#[test]
+fn titles_follow_one_pattern() {
+    assert_eq!(layout::page_title(&registry::HOME), "7GUIs");
+    assert_eq!(layout::page_title(&registry::COUNTER), "Counter · 7GUIs");
+    assert_eq!(layout::page_title(&registry::CRUD), "CRUD · 7GUIs");
+}
+
+#[test]
+fn every_page_has_the_standard_head() {
+    for page in registry::PAGES {
+        let html = render(page);
+
+        assert!(html.contains("<meta charset=\"utf-8\">"), "{}", page.slug);
+        assert!(
+            html.contains(
+                "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">"
+            ),
+            "{}",
+            page.slug
+        );
+        assert!(html.contains("href=\"/app.css\""), "{}", page.slug);
+    }
+}
+
+#[test]
+fn nav_links_to_every_gui_and_highlights_the_current_one() {
+    for page in registry::PAGES {
+        let html = render(page);
+
+        for other in registry::PAGES {
+            assert!(
+                html.contains(&format!("href=\"{}\"", other.route)),
+                "{} doesn't link to {}",
+                page.slug,
+                other.slug
+            );
+        }
+
+        let current = format!("href=\"{}\" aria-current=\"page\"", page.route);
+        if page.route == registry::HOME.route {
+            assert!(!html.contains("aria-current"));
+        } else {
+            assert!(html.contains(&current), "{} isn't highlighted", page.slug);
+            assert_eq!(html.matches("aria-current").count(), 1, "{}", page.slug);
+        }
+    }
+}
+
+#[test]
+fn layout_stays_inside_the_page_element() {
+    for page in registry::PAGES {
+        let html = render(page);
+        let body = html
+            .split_once("<body>")
+            .map(|(_, body)| body)
+            .unwrap_or(&html);
+
+        // polyester re-renders the page element, so nothing may live outside it
+        assert!(
+            body.trim_start().starts_with("<div id=\"sevenguis\""),
+            "{}",
+            page.slug
+        );
+        assert!(body.contains("<footer"), "{}", page.slug);
+    }
+}
@@ -0,0 +1,48 @@
+use sevenguis_core::page_error;
+use sevenguis_core::page_error::ErrorKind;
+use sevenguis_core::page_error::PageError;
+
+#[test]
+fn parse_errors_name_what_failed() {
+    let error = PageError::parse("fahrenheit", "invalid digit found in string");
+
+    assert_eq!(error.kind, ErrorKind::InvalidInput);
+    assert_eq!(error.context, "fahrenheit");
+    assert_eq!(
+        error.to_string(),
+        "Failed to parse fahrenheit: invalid digit found in string"
+    );
+}
+
+#[test]
+fn invalid_state_errors_show_their_message() {
+    let error = PageError::invalid_state("delete", "No visible person is selected");
+
+    assert_eq!(error.kind, ErrorKind::InvalidState);
+    assert_eq!(error.to_string(), "No visible person is selected");
+}
+
+#[test]
+fn banner_is_only_shown_for_an_error() {
+    assert!(page_error::view_banner(None).into_string().is_empty());
+
+    let error = PageError::parse("diameter", "<empty>");
+    let html = page_error::view_banner(Some(&error)).into_string();
+
+    assert!(html.contains("id=\"page-error\""));
+    assert!(html.contains("id=\"dismiss-error\""));
+    assert!(html.contains("Invalid input"));
+    assert!(html.contains("Failed to parse diameter: &lt;empty&gt;"));
+}
+
+#[test]
+fn errors_round_trip_through_json() {
+    let error = PageError::invalid_state("book", "Failed to book flight");
+    let json = serde_json::to_string(&error).unwrap();
+
+    assert_eq!(
+        json,
+        r#"{"kind":"invalidState","message":"Failed to book flight","context":"book"}"#
+    );
+    assert_eq!(serde_json::from_str::<PageError>(&json).unwrap(), error);
+}
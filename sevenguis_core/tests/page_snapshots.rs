@@ -0,0 +1,155 @@
+mod support;
+
+use polyester::time::Posix;
+use sevenguis_core::cells_page;
+use sevenguis_core::circle_page;
+use sevenguis_core::circle_page::Point;
+use sevenguis_core::clock::Clock;
+use sevenguis_core::clock::ManualClock;
+use sevenguis_core::counter_page;
+use sevenguis_core::crud_page;
+use sevenguis_core::flight_page;
+use sevenguis_core::flight_page::FlightType;
+use sevenguis_core::home_page;
+use sevenguis_core::spreadsheet::CellId;
+use sevenguis_core::temperature_page;
+use sevenguis_core::timer_page;
+use std::rc::Rc;
+use support::value;
+use support::Harness;
+
+// 2022-10-01T00:00:00Z, so time dependent pages render the same on every run
+const FIXED_TIME: i128 = 1_664_582_400_000;
+
+fn fixed_time(offset_millis: i128) -> Posix {
+    Posix::from_millis(FIXED_TIME + offset_millis)
+}
+
+#[test]
+fn home_page() {
+    Harness::new(home_page::HomePage {}).assert_snapshot("home_page");
+}
+
+#[test]
+fn counter_page() {
+    use counter_page::Msg;
+
+    let mut harness = Harness::new(counter_page::CounterPage {});
+    harness.assert_snapshot("counter_page");
+
+    harness.send_all([Msg::Increment, Msg::Increment, Msg::Undo]);
+    harness.assert_snapshot("counter_page_after_undo");
+}
+
+#[test]
+fn temperature_page() {
+    use temperature_page::Msg;
+
+    let mut harness = Harness::new(temperature_page::TemperaturePage {});
+    harness.assert_snapshot("temperature_page");
+
+    harness.send(Msg::CelsiusChanged("25".to_string()));
+    harness.assert_snapshot("temperature_page_converted");
+}
+
+#[test]
+fn flight_page() {
+    use flight_page::Msg;
+
+    let mut harness = Harness::new(flight_page::FlightPage {
+        clock: Rc::new(ManualClock::new(fixed_time(0))),
+    });
+    harness.assert_snapshot("flight_page");
+
+    harness.send_all([
+        Msg::FlightTypeChanged(value(FlightType::Return)),
+        Msg::StartDateChanged("2022-10-10".to_string()),
+        Msg::ReturnDateChanged("2022-10-05".to_string()),
+    ]);
+    harness.assert_snapshot("flight_page_invalid_return");
+
+    harness.send_all([
+        Msg::ReturnDateChanged("2022-10-20".to_string()),
+        Msg::BookFlight,
+    ]);
+    harness.assert_snapshot("flight_page_booked");
+}
+
+#[test]
+fn timer_page() {
+    use timer_page::Msg;
+
+    let clock = ManualClock::new(fixed_time(0));
+    let mut harness = Harness::new(timer_page::TimerPage {
+        clock: Rc::new(clock.clone()),
+    });
+    harness.assert_snapshot("timer_page");
+
+    clock.set(fixed_time(4200));
+    harness.send(Msg::OnTick(value(clock.now())));
+    harness.assert_snapshot("timer_page_running");
+
+    harness.send(Msg::LapClicked(1));
+    clock.set(fixed_time(6100));
+    harness.send_all([Msg::LapClicked(1), Msg::PauseClicked(1)]);
+    harness.assert_snapshot("timer_page_paused_with_laps");
+
+    harness.send(Msg::AddTimerClicked);
+    harness.assert_snapshot("timer_page_two_timers");
+
+    clock.set(fixed_time(9000));
+    harness.send(Msg::ModeChanged(2, value(timer_page::Mode::Countdown)));
+    harness.assert_snapshot("timer_page_countdown");
+
+    harness.send(Msg::MaxDurationChanged(1, "soon".to_string()));
+    harness.assert_snapshot("timer_page_error");
+}
+
+#[test]
+fn crud_page() {
+    use crud_page::Msg;
+
+    let mut harness = Harness::new(crud_page::CrudPage {});
+    harness.send(Msg::PeopleLoaded(None));
+    harness.assert_snapshot("crud_page");
+
+    harness.send_all([
+        Msg::FilterChanged("t".to_string()),
+        Msg::FirstNameChanged("".to_string()),
+    ]);
+    harness.assert_snapshot("crud_page_filtered_invalid");
+}
+
+#[test]
+fn circle_page() {
+    use circle_page::Msg;
+
+    let mut harness = Harness::new(circle_page::CirclePage {});
+    harness.assert_snapshot("circle_page");
+
+    harness.send_all([
+        Msg::CanvasClicked(Point { x: 100, y: 100 }),
+        Msg::CanvasClicked(Point { x: 200, y: 150 }),
+        Msg::CanvasRightClicked(Point { x: 100, y: 100 }),
+    ]);
+    harness.assert_snapshot("circle_page_dialog");
+}
+
+#[test]
+fn cells_page() {
+    use cells_page::Msg;
+
+    let cell = |s| CellId::parse(s).unwrap();
+    let mut harness = Harness::new(cells_page::CellsPage {});
+
+    harness.send_all([
+        Msg::EditStarted(cell("A0")),
+        Msg::EditCommitted("2".to_string()),
+        Msg::EditStarted(cell("A1")),
+        Msg::EditCommitted("=A0*21".to_string()),
+        Msg::EditStarted(cell("B0")),
+        Msg::EditCommitted("=1/0".to_string()),
+        Msg::EditStarted(cell("B1")),
+    ]);
+    harness.assert_snapshot("cells_page_editing");
+}
@@ -0,0 +1,76 @@
+use polyester::time::Posix;
+use sevenguis_core::clock::ManualClock;
+use sevenguis_core::layout;
+use sevenguis_core::registry;
+use std::collections::HashSet;
+use std::path::Path;
+use std::rc::Rc;
+
+// 2022-10-01T00:00:00Z
+const NOW: i128 = 1_664_582_400_000;
+
+#[test]
+fn every_page_renders() {
+    for page in registry::PAGES {
+        let html = (page.new)(Rc::new(ManualClock::new(Posix::from_millis(NOW))))
+            .render(None)
+            .unwrap();
+
+        assert!(
+            html.contains(&format!("<title>{}</title>", layout::page_title(page))),
+            "{} has the wrong title",
+            page.slug
+        );
+        assert!(html.contains("id=\"sevenguis\""), "{}", page.slug);
+
+        if let Some(script) = page.script {
+            assert!(
+                html.contains(&format!("src=\"{}\"", script)),
+                "{} doesn't load its script",
+                page.slug
+            );
+        }
+    }
+}
+
+#[test]
+fn slugs_and_routes_are_unique() {
+    let slugs: HashSet<_> = registry::PAGES.iter().map(|page| page.slug).collect();
+    let routes: HashSet<_> = registry::PAGES.iter().map(|page| page.route).collect();
+
+    assert_eq!(slugs.len(), registry::PAGES.len());
+    assert_eq!(routes.len(), registry::PAGES.len());
+
+    for page in registry::PAGES {
+        assert!(page.route.starts_with('/') && page.route.ends_with('/'));
+        assert_eq!(registry::find(page.slug).unwrap().route, page.route);
+        assert_eq!(registry::find_by_route(page.route).unwrap().slug, page.slug);
+    }
+}
+
+#[test]
+fn home_page_links_to_every_other_page() {
+    let html = (registry::HOME.new)(Rc::new(ManualClock::new(Posix::from_millis(NOW))))
+        .render(None)
+        .unwrap();
+
+    for page in registry::PAGES {
+        if page.route != registry::HOME.route {
+            assert!(html.contains(&format!("href=\"{}\"", page.route)));
+            assert!(html.contains(page.description));
+        }
+    }
+}
+
+// esbuild bundles sevenguis_web/src/<name>.ts into /<name>.js
+#[test]
+fn every_script_has_an_entrypoint() {
+    let web_src = Path::new(env!("CARGO_MANIFEST_DIR")).join("../sevenguis_web/src");
+
+    for script in registry::PAGES.iter().filter_map(|page| page.script) {
+        let name = script.trim_start_matches('/').trim_end_matches(".js");
+        let entrypoint = web_src.join(format!("{}.ts", name));
+
+        assert!(entrypoint.is_file(), "missing {}", entrypoint.display());
+    }
+}
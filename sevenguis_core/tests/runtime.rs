@@ -0,0 +1,80 @@
+use polyester::browser::Effects;
+use sevenguis_core::page_error::ErrorState;
+use sevenguis_core::page_error::PageError;
+use sevenguis_core::runtime;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Model {
+    steps: Vec<&'static str>,
+    error: Option<PageError>,
+}
+
+impl ErrorState for Model {
+    fn error(&self) -> Option<&PageError> {
+        self.error.as_ref()
+    }
+
+    fn set_error(&mut self, error: Option<PageError>) {
+        self.error = error;
+    }
+}
+
+fn model() -> Model {
+    Model {
+        steps: vec!["init"],
+        error: None,
+    }
+}
+
+fn step(model: &mut Model, name: &'static str) -> Result<Effects<(), ()>, PageError> {
+    model.steps.push(name);
+    Ok(vec![])
+}
+
+fn fail(_model: &mut Model, err: PageError) -> Result<Effects<(), ()>, PageError> {
+    Err(err)
+}
+
+#[test]
+fn successful_updates_are_kept() {
+    let mut model = model();
+
+    let effects = runtime::update(&mut model, |model| step(model, "one")).unwrap();
+
+    assert!(effects.is_empty());
+    assert_eq!(model.steps, ["init", "one"]);
+    assert_eq!(model.error, None);
+}
+
+#[test]
+fn failed_updates_are_rolled_back() {
+    let mut model = model();
+
+    let result = runtime::update(&mut model, |model| {
+        step(model, "half")?;
+        fail(model, PageError::parse("step", "nope"))
+    });
+
+    assert!(result.unwrap().is_empty());
+    assert_eq!(model.steps, ["init"]);
+    assert_eq!(model.error, Some(PageError::parse("step", "nope")));
+}
+
+#[test]
+fn errors_stay_until_replaced() {
+    let mut model = model();
+
+    let _ = runtime::update(&mut model, |model| {
+        fail(model, PageError::invalid_state("first", "first"))
+    });
+    let _ = runtime::update(&mut model, |model| step(model, "two"));
+
+    assert_eq!(model.steps, ["init", "two"]);
+    assert_eq!(model.error.as_ref().unwrap().context, "first");
+
+    let _ = runtime::update(&mut model, |model| {
+        fail(model, PageError::invalid_state("second", "second"))
+    });
+
+    assert_eq!(model.error.as_ref().unwrap().context, "second");
+}
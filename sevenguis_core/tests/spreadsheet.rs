@@ -0,0 +1,234 @@
+use sevenguis_core::spreadsheet::CellError;
+use sevenguis_core::spreadsheet::CellId;
+use sevenguis_core::spreadsheet::CellValue;
+use sevenguis_core::spreadsheet::Sheet;
+use sevenguis_core::spreadsheet::COLUMNS;
+use sevenguis_core::spreadsheet::ROWS;
+
+fn cell(s: &str) -> CellId {
+    CellId::parse(s).unwrap()
+}
+
+fn value(sheet: &Sheet, s: &str) -> CellValue {
+    sheet.value(&cell(s))
+}
+
+#[test]
+fn parses_cell_ids() {
+    assert_eq!(CellId::parse("A0"), CellId::new(0, 0));
+    assert_eq!(CellId::parse("z99"), CellId::new(25, 99));
+    assert_eq!(CellId::parse("A100"), None);
+    assert_eq!(CellId::parse("AA1"), None);
+    assert_eq!(CellId::parse("1A"), None);
+    assert_eq!(cell("C12").to_string(), "C12");
+}
+
+#[test]
+fn literals() {
+    let mut sheet = Sheet::new();
+    sheet.set(cell("A0"), "42");
+    sheet.set(cell("A1"), "hello");
+    sheet.set(cell("A2"), "");
+
+    assert_eq!(value(&sheet, "A0"), CellValue::Number(42.0));
+    assert_eq!(value(&sheet, "A1"), CellValue::Text("hello".to_string()));
+    assert_eq!(value(&sheet, "A2"), CellValue::Empty);
+    assert_eq!(sheet.content(&cell("A1")), "hello");
+}
+
+#[test]
+fn arithmetic_formulas() {
+    let mut sheet = Sheet::new();
+    sheet.set(cell("A1"), "2");
+    sheet.set(cell("B2"), "3");
+    sheet.set(cell("C0"), "=A1+B2");
+    sheet.set(cell("C1"), "=(A1 + B2) * 2 - -1");
+    sheet.set(cell("C2"), "=B2 / A1");
+
+    assert_eq!(value(&sheet, "C0"), CellValue::Number(5.0));
+    assert_eq!(value(&sheet, "C1"), CellValue::Number(11.0));
+    assert_eq!(value(&sheet, "C2"), CellValue::Number(1.5));
+}
+
+#[test]
+fn range_functions() {
+    let mut sheet = Sheet::new();
+    for (row, n) in ["1", "2", "3", "4"].iter().enumerate() {
+        sheet.set(CellId::new(0, row as u8 + 1).unwrap(), n);
+    }
+
+    sheet.set(cell("B0"), "=sum(A1:A10)");
+    sheet.set(cell("B1"), "=avg(A1:A4)");
+    sheet.set(cell("B2"), "=max(A1:A4, 10)");
+    sheet.set(cell("B3"), "=count(A0:A10)");
+    sheet.set(cell("B4"), "=PROD(A1:A4)");
+
+    assert_eq!(value(&sheet, "B0"), CellValue::Number(10.0));
+    assert_eq!(value(&sheet, "B1"), CellValue::Number(2.5));
+    assert_eq!(value(&sheet, "B2"), CellValue::Number(10.0));
+    assert_eq!(value(&sheet, "B3"), CellValue::Number(4.0));
+    assert_eq!(value(&sheet, "B4"), CellValue::Number(24.0));
+}
+
+#[test]
+fn dependents_are_recomputed() {
+    let mut sheet = Sheet::new();
+    sheet.set(cell("A0"), "1");
+    sheet.set(cell("A1"), "=A0*10");
+    sheet.set(cell("A2"), "=A1+A0");
+    sheet.set(cell("A3"), "=sum(A0:A2)");
+
+    assert_eq!(value(&sheet, "A3"), CellValue::Number(22.0));
+
+    sheet.set(cell("A0"), "2");
+
+    assert_eq!(value(&sheet, "A1"), CellValue::Number(20.0));
+    assert_eq!(value(&sheet, "A2"), CellValue::Number(22.0));
+    assert_eq!(value(&sheet, "A3"), CellValue::Number(44.0));
+
+    sheet.set(cell("A1"), "5");
+
+    assert_eq!(value(&sheet, "A2"), CellValue::Number(7.0));
+    assert!(sheet.dependents(&cell("A0")).contains(&cell("A2")));
+    assert!(!sheet.dependents(&cell("A0")).contains(&cell("A1")));
+}
+
+#[test]
+fn empty_references_count_as_zero() {
+    let mut sheet = Sheet::new();
+    sheet.set(cell("A0"), "=B0+1");
+
+    assert_eq!(value(&sheet, "A0"), CellValue::Number(1.0));
+
+    sheet.set(cell("B0"), "4");
+    assert_eq!(value(&sheet, "A0"), CellValue::Number(5.0));
+
+    sheet.set(cell("B0"), "");
+    assert_eq!(value(&sheet, "A0"), CellValue::Number(1.0));
+}
+
+#[test]
+fn cycles_are_reported() {
+    let mut sheet = Sheet::new();
+    sheet.set(cell("A0"), "=A0+1");
+    assert_eq!(value(&sheet, "A0"), CellValue::Error(CellError::Cycle));
+
+    sheet.set(cell("B0"), "=B1");
+    sheet.set(cell("B1"), "=B2");
+    sheet.set(cell("B2"), "=B0");
+    sheet.set(cell("C0"), "=B1*2");
+
+    assert_eq!(value(&sheet, "B0"), CellValue::Error(CellError::Cycle));
+    assert_eq!(value(&sheet, "B1"), CellValue::Error(CellError::Cycle));
+    assert_eq!(value(&sheet, "B2"), CellValue::Error(CellError::Cycle));
+    assert_eq!(value(&sheet, "C0"), CellValue::Error(CellError::Cycle));
+
+    sheet.set(cell("B2"), "3");
+
+    assert_eq!(value(&sheet, "B0"), CellValue::Number(3.0));
+    assert_eq!(value(&sheet, "B1"), CellValue::Number(3.0));
+    assert_eq!(value(&sheet, "C0"), CellValue::Number(6.0));
+}
+
+#[test]
+fn range_cycles_are_reported() {
+    let mut sheet = Sheet::new();
+    sheet.set(cell("A0"), "1");
+    sheet.set(cell("A5"), "=sum(A0:A9)");
+
+    assert_eq!(value(&sheet, "A5"), CellValue::Error(CellError::Cycle));
+}
+
+#[test]
+fn long_dependency_chains_are_recomputed() {
+    let mut sheet = Sheet::new();
+    let ids: Vec<CellId> = (0..COLUMNS)
+        .flat_map(|column| (0..ROWS).map(move |row| CellId::new(column, row).unwrap()))
+        .collect();
+
+    // A0 -> A1 -> ... -> Z99, every cell one more than the one before
+    sheet.set(ids[0], "1");
+    for pair in ids.windows(2) {
+        sheet.set(pair[1], &format!("={}+1", pair[0]));
+    }
+
+    assert_eq!(value(&sheet, "Z99"), CellValue::Number(2600.0));
+
+    sheet.set(cell("A0"), "=Z99");
+    assert_eq!(value(&sheet, "A0"), CellValue::Error(CellError::Cycle));
+    assert_eq!(value(&sheet, "Z99"), CellValue::Error(CellError::Cycle));
+
+    sheet.set(cell("A0"), "10");
+    assert_eq!(value(&sheet, "Z99"), CellValue::Number(2609.0));
+}
+
+#[test]
+fn deeply_nested_formulas_are_rejected() {
+    let mut sheet = Sheet::new();
+    let depth = 20_000;
+    sheet.set(
+        cell("A0"),
+        &format!("={}1{}", "(".repeat(depth), ")".repeat(depth)),
+    );
+    sheet.set(cell("A1"), &format!("={}1", "-".repeat(depth)));
+    sheet.set(cell("A2"), &format!("=1{}", "+1".repeat(depth)));
+    sheet.set(
+        cell("A3"),
+        &format!("={}1{}", "sum(".repeat(depth), ")".repeat(depth)),
+    );
+    sheet.set(
+        cell("A4"),
+        &format!("={}1{}", "(".repeat(100), ")".repeat(100)),
+    );
+
+    for id in ["A0", "A1", "A2", "A3"] {
+        assert_eq!(
+            value(&sheet, id),
+            CellValue::Error(CellError::Parse),
+            "{}",
+            id
+        );
+    }
+    assert_eq!(value(&sheet, "A4"), CellValue::Number(1.0));
+}
+
+#[test]
+fn errors() {
+    let mut sheet = Sheet::new();
+    sheet.set(cell("A0"), "text");
+    sheet.set(cell("A1"), "=A0+1");
+    sheet.set(cell("A2"), "=1/0");
+    sheet.set(cell("A3"), "=1+");
+    sheet.set(cell("A4"), "=foo(A1)");
+    sheet.set(cell("A5"), "=A2*2");
+    sheet.set(cell("A6"), "=A0:A2");
+
+    assert_eq!(value(&sheet, "A1"), CellValue::Error(CellError::Value));
+    assert_eq!(
+        value(&sheet, "A2"),
+        CellValue::Error(CellError::DivisionByZero)
+    );
+    assert_eq!(value(&sheet, "A3"), CellValue::Error(CellError::Parse));
+    assert_eq!(value(&sheet, "A4"), CellValue::Error(CellError::Parse));
+    assert_eq!(
+        value(&sheet, "A5"),
+        CellValue::Error(CellError::DivisionByZero)
+    );
+    assert_eq!(value(&sheet, "A6"), CellValue::Error(CellError::Value));
+    assert_eq!(value(&sheet, "A2").to_string(), "#DIV/0!");
+}
+
+#[test]
+fn sheet_roundtrips_through_json() {
+    let mut sheet = Sheet::new();
+    sheet.set(cell("A0"), "1");
+    sheet.set(cell("A1"), "=A0+1");
+
+    let json = serde_json::to_string(&sheet).unwrap();
+    let mut sheet: Sheet = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(value(&sheet, "A1"), CellValue::Number(2.0));
+
+    sheet.set(cell("A0"), "5");
+    assert_eq!(value(&sheet, "A1"), CellValue::Number(6.0));
+}
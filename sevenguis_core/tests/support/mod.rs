@@ -0,0 +1,357 @@
+// Shared by the page suites, not every suite uses every helper
+#![allow(dead_code)]
+
+pub mod snapshot;
+
+use polyester::browser::Effect;
+use polyester::browser::Effects;
+use polyester::browser::Value;
+use polyester::page::Page;
+use serde::Serialize;
+use sevenguis_core::page_error::ErrorState;
+use sevenguis_core::page_error::PageError;
+use std::marker::PhantomData;
+
+// Drives a page the way the browser runtime does, without a browser
+pub struct Harness<P, Model, Msg, AppEffect> {
+    page: P,
+    model: Model,
+    effects: Effects<Msg, AppEffect>,
+    // The browser runtime renders after every update, so the harness does too
+    html: String,
+    updates: usize,
+    renders: usize,
+    msg: PhantomData<Msg>,
+}
+
+impl<P, Model, Msg, AppEffect> Harness<P, Model, Msg, AppEffect>
+where
+    P: Page<Model, Msg, AppEffect>,
+    Msg: Serialize,
+    AppEffect: Serialize,
+{
+    pub fn new(page: P) -> Self {
+        let (model, effects) = page.init();
+        let html = page.view(&model).to_markup().into_string();
+
+        Harness {
+            page,
+            model,
+            effects,
+            html,
+            updates: 0,
+            renders: 1,
+            msg: PhantomData,
+        }
+    }
+
+    // Effects always belong to the last message sent
+    pub fn send(&mut self, msg: Msg) -> &mut Self {
+        // Pages report errors through their model, the browser runtime would throw on this
+        match self.page.update(&msg, &mut self.model) {
+            Ok(effects) => self.effects = effects,
+            Err(err) => panic!("update failed without reporting the error: {}", err),
+        }
+
+        self.updates += 1;
+        self.html = self.page.view(&self.model).to_markup().into_string();
+        self.renders += 1;
+
+        self
+    }
+
+    pub fn send_all(&mut self, msgs: impl IntoIterator<Item = Msg>) -> &mut Self {
+        for msg in msgs {
+            self.send(msg);
+        }
+
+        self
+    }
+
+    pub fn model(&self) -> &Model {
+        &self.model
+    }
+
+    // Counted since the page was created, init included for renders
+    pub fn updates(&self) -> usize {
+        self.updates
+    }
+
+    pub fn renders(&self) -> usize {
+        self.renders
+    }
+
+    // Effects are opaque, so they are compared through their serialized form
+    pub fn assert_effects(&self, expected: Effects<Msg, AppEffect>) {
+        assert_eq!(to_json(&self.effects), to_json(&expected));
+    }
+
+    // Whether the last message produced this effect, among any others
+    pub fn has_effect(&self, expected: Effect<Msg, AppEffect>) -> bool {
+        let expected = to_json(&expected);
+        self.effects
+            .iter()
+            .any(|effect| to_json(effect) == expected)
+    }
+
+    pub fn assert_no_effects(&self) {
+        assert!(self.effects.is_empty(), "expected no effects");
+    }
+
+    pub fn html(&self) -> String {
+        self.html.clone()
+    }
+
+    pub fn find(&self, id: impl ToString) -> Option<Element> {
+        Element::find(&self.html(), "id", &id.to_string())
+    }
+
+    // For elements that are identified by something other than their id, like data attributes
+    pub fn find_by(&self, attr: &str, value: &str) -> Option<Element> {
+        Element::find(&self.html(), attr, value)
+    }
+
+    pub fn element(&self, id: impl ToString) -> Element {
+        let id = id.to_string();
+        self.find(&id)
+            .unwrap_or_else(|| panic!("no element with id {}", id))
+    }
+
+    pub fn assert_snapshot(&self, name: &str) {
+        snapshot::assert_snapshot(name, &self.page.view(&self.model));
+    }
+
+    pub fn exists(&self, id: impl ToString) -> bool {
+        self.find(id).is_some()
+    }
+
+    pub fn contains_text(&self, text: &str) -> bool {
+        strip_tags(&self.html()).contains(text)
+    }
+}
+
+// The error the page is showing, which stays until it's dismissed or replaced
+impl<P, Model, Msg, AppEffect> Harness<P, Model, Msg, AppEffect>
+where
+    P: Page<Model, Msg, AppEffect>,
+    Model: ErrorState,
+    Msg: Serialize,
+    AppEffect: Serialize,
+{
+    pub fn error(&self) -> Option<&PageError> {
+        self.model.error()
+    }
+
+    pub fn assert_ok(&self) {
+        assert_eq!(self.error(), None);
+    }
+
+    pub fn assert_error(&self) -> &PageError {
+        self.error().expect("expected the page to show an error")
+    }
+}
+
+// Builds the Value the browser would send, e.g. for on_change or time effects
+pub fn value<T: Serialize>(value: T) -> Value {
+    serde_json::from_value(to_json(&value)).expect("value should deserialize")
+}
+
+fn to_json<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).expect("effects should serialize")
+}
+
+#[derive(Debug, Clone)]
+pub struct Element {
+    pub tag: String,
+    pub attributes: Vec<(String, Option<String>)>,
+    pub inner_html: String,
+}
+
+impl Element {
+    // A small scanner for the markup maud produces, not a general html parser
+    pub fn find(html: &str, attr: &str, value: &str) -> Option<Element> {
+        let needle = format!(" {}=\"{}\"", attr, value);
+        let mut offset = 0;
+
+        while let Some(found) = html[offset..].find(&needle) {
+            let position = offset + found;
+            let tag_start = html[..position].rfind('<')?;
+
+            if !html[tag_start..position].contains('>') {
+                return Some(Element::parse(html, tag_start));
+            }
+
+            offset = position + needle.len();
+        }
+
+        None
+    }
+
+    fn parse(html: &str, start: usize) -> Element {
+        let (tag, attributes, tag_end) = parse_start_tag(html, start);
+
+        let inner_html = if is_void(&tag) {
+            String::new()
+        } else {
+            let end = matching_end_tag(html, &tag, tag_end);
+            html[tag_end..end].to_string()
+        };
+
+        Element {
+            tag,
+            attributes,
+            inner_html,
+        }
+    }
+
+    pub fn attr(&self, name: &str) -> Option<String> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.clone().unwrap_or_default())
+    }
+
+    pub fn has_attr(&self, name: &str) -> bool {
+        self.attributes.iter().any(|(n, _)| n == name)
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.has_attr("disabled")
+    }
+
+    pub fn is_invalid(&self) -> bool {
+        self.attr("aria-invalid").as_deref() == Some("true")
+    }
+
+    pub fn text(&self) -> String {
+        strip_tags(&self.inner_html).trim().to_string()
+    }
+
+    // The value a user would see in the control
+    pub fn value(&self) -> String {
+        match self.tag.as_str() {
+            "textarea" => unescape(&self.inner_html),
+            "select" => self
+                .selected_option()
+                .and_then(|option| option.attr("value"))
+                .unwrap_or_default(),
+            _ => self.attr("value").unwrap_or_default(),
+        }
+    }
+
+    pub fn options(&self) -> Vec<Element> {
+        let mut options = vec![];
+        let mut offset = 0;
+
+        while let Some(found) = self.inner_html[offset..].find("<option") {
+            let option = Element::parse(&self.inner_html, offset + found);
+            offset += found + "<option".len();
+            options.push(option);
+        }
+
+        options
+    }
+
+    pub fn selected_option(&self) -> Option<Element> {
+        self.options()
+            .into_iter()
+            .find(|option| option.has_attr("selected"))
+    }
+}
+
+fn parse_start_tag(html: &str, start: usize) -> (String, Vec<(String, Option<String>)>, usize) {
+    let bytes = html.as_bytes();
+    let mut i = start + 1;
+
+    let name_start = i;
+    while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+        i += 1;
+    }
+    let tag = html[name_start..i].to_string();
+
+    let mut attributes = vec![];
+
+    loop {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+
+        if i >= bytes.len() || bytes[i] == b'>' {
+            break;
+        }
+
+        let attr_start = i;
+        while i < bytes.len() && !matches!(bytes[i], b'=' | b'>') && !bytes[i].is_ascii_whitespace()
+        {
+            i += 1;
+        }
+        let name = html[attr_start..i].to_string();
+
+        if i < bytes.len() && bytes[i] == b'=' {
+            let value_start = i + 2;
+            let value_end = value_start + html[value_start..].find('"').unwrap_or(0);
+            attributes.push((name, Some(unescape(&html[value_start..value_end]))));
+            i = value_end + 1;
+        } else {
+            attributes.push((name, None));
+        }
+    }
+
+    (tag, attributes, (i + 1).min(html.len()))
+}
+
+fn matching_end_tag(html: &str, tag: &str, from: usize) -> usize {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+    let mut depth = 1;
+    let mut i = from;
+
+    while i < html.len() {
+        let rest = &html[i..];
+
+        if rest.starts_with(&close) {
+            depth -= 1;
+            if depth == 0 {
+                return i;
+            }
+            i += close.len();
+        } else if rest.starts_with(&open)
+            && matches!(rest.as_bytes().get(open.len()), Some(b' ' | b'>'))
+        {
+            depth += 1;
+            i += open.len();
+        } else {
+            i += rest.chars().next().map(char::len_utf8).unwrap_or(1);
+        }
+    }
+
+    html.len()
+}
+
+fn is_void(tag: &str) -> bool {
+    matches!(tag, "input" | "meta" | "link" | "br" | "hr" | "img")
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    unescape(&text)
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,68 @@
+use polyester::page::PageMarkup;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+// Set to rewrite the golden files from the current output instead of comparing against them
+const UPDATE_VAR: &str = "UPDATE_SNAPSHOTS";
+
+pub fn assert_snapshot(name: &str, markup: &PageMarkup) {
+    let path = snapshot_path(name);
+    let actual = render(markup);
+
+    if env::var_os(UPDATE_VAR).is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual)
+            .unwrap_or_else(|err| panic!("Failed to write {}: {}", path.display(), err));
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "Missing snapshot {}, run the tests with {}=1 to create it",
+            path.display(),
+            UPDATE_VAR
+        )
+    });
+
+    if let Some((line, expected_line, actual_line)) = first_difference(&expected, &actual) {
+        panic!(
+            "Snapshot {} differs at line {}\n  expected: {}\n    actual: {}\nRun the tests with {}=1 if the change is intended",
+            name, line, expected_line, actual_line, UPDATE_VAR
+        );
+    }
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.html", name))
+}
+
+// One tag per line keeps the golden files reviewable in diffs
+fn render(markup: &PageMarkup) -> String {
+    format!("{}\n\n{}\n", markup.head.0, markup.body.0).replace("><", ">\n<")
+}
+
+fn first_difference(expected: &str, actual: &str) -> Option<(usize, String, String)> {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => return None,
+
+            (e, a) if e != a => {
+                return Some((
+                    line,
+                    e.unwrap_or("<end of file>").to_string(),
+                    a.unwrap_or("<end of file>").to_string(),
+                ));
+            }
+
+            _ => line += 1,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+mod support;
+
+use sevenguis_core::temperature_page::Id;
+use sevenguis_core::temperature_page::Msg;
+use sevenguis_core::temperature_page::TemperaturePage;
+use support::Harness;
+
+#[test]
+fn starts_empty() {
+    let harness = Harness::new(TemperaturePage {});
+
+    assert_eq!(harness.element(Id::Celsius).value(), "");
+    assert_eq!(harness.element(Id::Fahrenheit).value(), "");
+}
+
+#[test]
+fn celsius_updates_fahrenheit() {
+    let mut harness = Harness::new(TemperaturePage {});
+
+    harness.send(Msg::CelsiusChanged("100".to_string()));
+    harness.assert_ok();
+    assert_eq!(harness.element(Id::Fahrenheit).value(), "212");
+
+    harness.send(Msg::CelsiusChanged("-40".to_string()));
+    assert_eq!(harness.element(Id::Fahrenheit).value(), "-40");
+}
+
+#[test]
+fn fahrenheit_updates_celsius() {
+    let mut harness = Harness::new(TemperaturePage {});
+
+    harness.send(Msg::FahrenheitChanged("50".to_string()));
+    harness.assert_ok();
+    assert_eq!(harness.element(Id::Celsius).value(), "10");
+    assert_eq!(harness.element(Id::Fahrenheit).value(), "50");
+}
+
+#[test]
+fn invalid_input_clears_the_other_field() {
+    let mut harness = Harness::new(TemperaturePage {});
+
+    harness.send(Msg::CelsiusChanged("5".to_string()));
+    harness.send(Msg::CelsiusChanged("5x".to_string()));
+
+    harness.assert_ok();
+    assert_eq!(harness.model().celsius, Some(5));
+    assert_eq!(harness.element(Id::Fahrenheit).value(), "");
+}
@@ -0,0 +1,1003 @@
+mod support;
+
+use polyester::browser;
+use polyester::browser::DomId;
+use polyester::time::Posix;
+use sevenguis_core::clock::ManualClock;
+use sevenguis_core::page_error;
+use sevenguis_core::page_error::ErrorKind;
+use sevenguis_core::page_error::PageError;
+use sevenguis_core::runtime;
+use sevenguis_core::timer_page;
+use sevenguis_core::timer_page::AppEffect;
+use sevenguis_core::timer_page::Id;
+use sevenguis_core::timer_page::Mode;
+use sevenguis_core::timer_page::Model;
+use sevenguis_core::timer_page::Msg;
+use sevenguis_core::timer_page::StoredTimers;
+use sevenguis_core::timer_page::Timer;
+use sevenguis_core::timer_page::TimerPage;
+use std::rc::Rc;
+use std::time::Duration;
+use support::value;
+use support::Harness;
+
+const START: i128 = 1_664_582_400_000;
+
+// Sends every message at a given time, so clicks and ticks agree on the clock
+struct Timers {
+    harness: Harness<TimerPage, Model, Msg, AppEffect>,
+    clock: ManualClock,
+}
+
+impl Timers {
+    fn at(&mut self, millis: i128, msg: Msg) -> &mut Self {
+        self.clock.set(Posix::from_millis(START + millis));
+        self.harness.send(msg);
+        self
+    }
+
+    fn tick(&mut self, millis: i128) -> &mut Self {
+        let now = Posix::from_millis(START + millis);
+        self.at(millis, Msg::OnTick(value(now)))
+    }
+
+    fn ticks(&mut self, millis: &[i128]) -> &mut Self {
+        for millis in millis {
+            self.tick(*millis);
+        }
+
+        self
+    }
+
+    fn timer(&self, id: u32) -> &Timer {
+        let timers = &self.harness.model().timers;
+        timers.iter().find(|timer| timer.id == id).unwrap()
+    }
+
+    // As shown, at the time of the latest message
+    fn elapsed(&self, id: u32) -> Duration {
+        self.timer(id).elapsed(self.harness.model().current_time)
+    }
+}
+
+fn timers() -> Timers {
+    let clock = ManualClock::new(Posix::from_millis(START));
+    let harness = Harness::new(TimerPage {
+        clock: Rc::new(clock.clone()),
+    });
+
+    Timers { harness, clock }
+}
+
+// The timer the page starts with
+fn dom_id(id: Id) -> DomId {
+    timer_page::timer_dom_id(id, 1)
+}
+
+#[test]
+fn starts_with_nothing_elapsed() {
+    let t = timers();
+
+    assert_eq!(t.elapsed(1), Duration::from_secs(0));
+    assert_eq!(
+        t.harness
+            .element(dom_id(Id::Elapsed))
+            .attr("value")
+            .unwrap(),
+        "0"
+    );
+    assert_eq!(
+        t.harness.element(dom_id(Id::Elapsed)).attr("max").unwrap(),
+        "15000"
+    );
+    assert_eq!(t.harness.element(dom_id(Id::Duration)).value(), "15000");
+    assert!(t.harness.contains_text("0.00s / 15.00s"));
+}
+
+#[test]
+fn ticks_show_elapsed_time() {
+    let mut t = timers();
+
+    t.ticks(&[200, 400, 1000]);
+
+    t.harness.assert_ok();
+    assert_eq!(t.elapsed(1), Duration::from_millis(1000));
+    assert_eq!(
+        t.harness
+            .element(dom_id(Id::Elapsed))
+            .attr("value")
+            .unwrap(),
+        "1000"
+    );
+    assert!(t.harness.contains_text("1.00s / 15.00s"));
+}
+
+#[test]
+fn fifteen_seconds_of_ticks_fill_the_default_duration() {
+    let mut t = timers();
+
+    let ticks = t
+        .clock
+        .ticks(Duration::from_secs(15), Duration::from_millis(100));
+    t.harness
+        .send_all(ticks.into_iter().map(|now| Msg::OnTick(value(now))));
+
+    assert_eq!(t.elapsed(1), Duration::from_secs(15));
+    assert_eq!(
+        t.harness
+            .element(dom_id(Id::Elapsed))
+            .attr("value")
+            .unwrap(),
+        "15000"
+    );
+    assert!(t.harness.contains_text("15.00s / 15.00s"));
+}
+
+#[test]
+fn irregular_ticks_cause_no_drift() {
+    let mut t = timers();
+
+    // Late, bunched up and throttled ticks, like in a background tab
+    t.ticks(&[230, 250, 251, 1900, 2000, 7013, 7014, 9000]);
+
+    assert_eq!(t.elapsed(1), Duration::from_millis(9000));
+}
+
+#[test]
+fn missed_ticks_are_caught_up_by_the_next_message() {
+    let mut t = timers();
+
+    t.tick(200).at(6400, Msg::PauseClicked(1));
+
+    assert_eq!(t.elapsed(1), Duration::from_millis(6400));
+}
+
+#[test]
+fn a_tick_from_before_the_start_counts_as_nothing() {
+    let mut t = timers();
+
+    t.tick(-300);
+
+    assert_eq!(t.elapsed(1), Duration::from_secs(0));
+}
+
+#[test]
+fn elapsed_time_stops_at_the_duration() {
+    let mut t = timers();
+
+    t.ticks(&[10_000, 20_000]);
+
+    assert_eq!(t.elapsed(1), Duration::from_secs(15));
+}
+
+#[test]
+fn raising_the_duration_resumes_the_timer() {
+    let mut t = timers();
+
+    t.tick(20_000)
+        .at(20_000, Msg::MaxDurationChanged(1, "25000".to_string()))
+        .tick(22_000);
+
+    assert_eq!(t.elapsed(1), Duration::from_secs(17));
+}
+
+#[test]
+fn moving_the_slider_neither_drops_nor_double_counts_time() {
+    let mut t = timers();
+
+    t.tick(1000)
+        .at(1150, Msg::MaxDurationChanged(1, "20000".to_string()))
+        .at(1170, Msg::MaxDurationChanged(1, "21000".to_string()))
+        .tick(1200);
+
+    assert_eq!(t.elapsed(1), Duration::from_millis(1200));
+}
+
+#[test]
+fn huge_durations_keep_the_slider_in_range() {
+    let mut t = timers();
+
+    t.at(0, Msg::MaxDurationChanged(1, u64::MAX.to_string()));
+
+    t.harness.assert_ok();
+    assert_eq!(
+        t.harness.element(dom_id(Id::Duration)).attr("max"),
+        Some(i64::MAX.to_string())
+    );
+}
+
+#[test]
+fn lowering_the_duration_caps_elapsed_time() {
+    let mut t = timers();
+
+    t.tick(8000)
+        .at(8000, Msg::MaxDurationChanged(1, "5000".to_string()));
+
+    assert_eq!(t.elapsed(1), Duration::from_secs(5));
+    assert_eq!(t.harness.element(dom_id(Id::Duration)).value(), "5000");
+}
+
+#[test]
+fn reset_starts_over() {
+    let mut t = timers();
+
+    t.tick(3000).at(3000, Msg::ResetClicked(1)).tick(3500);
+
+    assert_eq!(t.elapsed(1), Duration::from_millis(500));
+}
+
+#[test]
+fn pause_freezes_elapsed_time() {
+    let mut t = timers();
+
+    t.tick(1000).at(1100, Msg::PauseClicked(1));
+
+    // The time since the last tick counts too
+    assert_eq!(t.elapsed(1), Duration::from_millis(1100));
+    assert_eq!(t.harness.element(dom_id(Id::PauseResume)).text(), "Resume");
+    assert!(t.harness.element(dom_id(Id::Lap)).is_disabled());
+
+    t.ticks(&[1200, 5000]);
+
+    assert_eq!(t.elapsed(1), Duration::from_millis(1100));
+}
+
+#[test]
+fn resume_continues_without_the_paused_time() {
+    let mut t = timers();
+
+    t.tick(1000)
+        .at(1000, Msg::PauseClicked(1))
+        .at(9000, Msg::ResumeClicked(1))
+        .tick(9500);
+
+    assert_eq!(t.elapsed(1), Duration::from_millis(1500));
+    assert_eq!(t.harness.element(dom_id(Id::PauseResume)).text(), "Pause");
+}
+
+#[test]
+fn pausing_twice_is_an_error() {
+    let mut t = timers();
+
+    t.at(0, Msg::PauseClicked(1)).at(0, Msg::PauseClicked(1));
+
+    assert_eq!(t.harness.assert_error().context, "pause");
+    assert!(t.timer(1).is_paused());
+}
+
+#[test]
+fn laps_show_delta_and_cumulative_time() {
+    let mut t = timers();
+
+    t.at(1200, Msg::LapClicked(1))
+        .ticks(&[2000, 2900])
+        .at(3000, Msg::LapClicked(1));
+
+    assert_eq!(
+        t.timer(1).laps,
+        vec![Duration::from_millis(1200), Duration::from_millis(3000)]
+    );
+
+    let second_lap = t.harness.find_by("data-lap", "2").unwrap();
+    assert_eq!(second_lap.text(), "21.80s3.00s");
+}
+
+#[test]
+fn lap_while_paused_is_an_error() {
+    let mut t = timers();
+
+    t.at(1000, Msg::PauseClicked(1))
+        .at(1000, Msg::LapClicked(1));
+
+    assert_eq!(t.harness.assert_error().context, "lap");
+    assert!(t.timer(1).laps.is_empty());
+}
+
+#[test]
+fn reset_clears_laps() {
+    let mut t = timers();
+
+    t.at(1000, Msg::LapClicked(1))
+        .at(1000, Msg::ResetClicked(1));
+
+    assert!(t.timer(1).laps.is_empty());
+    assert!(t.harness.find_by("data-lap", "1").is_none());
+}
+
+#[test]
+fn export_downloads_laps_as_csv() {
+    let mut t = timers();
+
+    assert!(t.harness.element(dom_id(Id::ExportLaps)).is_disabled());
+
+    t.at(1200, Msg::LapClicked(1))
+        .at(3000, Msg::LapClicked(1))
+        .at(3000, Msg::ExportLapsClicked(1));
+
+    t.harness
+        .assert_effects(vec![browser::app_effect(AppEffect::Download {
+            filename: "laps-1.csv".to_string(),
+            mime_type: "text/csv".to_string(),
+            content: "lap,delta_seconds,total_seconds\n1,1.200,1.200\n2,1.800,3.000\n".to_string(),
+        })]);
+}
+
+#[test]
+fn one_tick_advances_every_running_timer() {
+    let mut t = timers();
+
+    t.at(0, Msg::AddTimerClicked)
+        .at(0, Msg::AddTimerClicked)
+        .at(0, Msg::PauseClicked(2))
+        .tick(1000);
+
+    assert_eq!(t.elapsed(1), Duration::from_secs(1));
+    assert_eq!(t.elapsed(2), Duration::from_secs(0));
+    assert_eq!(t.elapsed(3), Duration::from_secs(1));
+}
+
+#[test]
+fn timers_have_their_own_duration() {
+    let mut t = timers();
+
+    t.at(0, Msg::AddTimerClicked)
+        .at(0, Msg::MaxDurationChanged(2, "2000".to_string()))
+        .tick(5000);
+
+    assert_eq!(t.elapsed(1), Duration::from_secs(5));
+    assert_eq!(t.elapsed(2), Duration::from_secs(2));
+    assert!(t.harness.contains_text("2.00s / 2.00s"));
+}
+
+#[test]
+fn added_timers_start_now() {
+    let mut t = timers();
+
+    t.tick(3000).at(3100, Msg::AddTimerClicked).tick(3200);
+
+    assert_eq!(t.elapsed(2), Duration::from_millis(100));
+    assert!(t.harness.exists(timer_page::timer_dom_id(Id::Timer, 2)));
+}
+
+#[test]
+fn timers_can_be_renamed_and_removed() {
+    let mut t = timers();
+
+    t.at(0, Msg::AddTimerClicked)
+        .at(0, Msg::NameChanged(2, "Tea".to_string()));
+
+    assert_eq!(
+        t.harness
+            .element(timer_page::timer_dom_id(Id::Name, 2))
+            .value(),
+        "Tea"
+    );
+
+    t.at(0, Msg::RemoveClicked(1));
+
+    assert_eq!(t.harness.model().timers.len(), 1);
+    assert_eq!(t.timer(2).name, "Tea");
+    assert!(!t.harness.exists(dom_id(Id::Timer)));
+}
+
+#[test]
+fn removing_an_unknown_timer_is_an_error() {
+    let mut t = timers();
+
+    t.at(0, Msg::RemoveClicked(7));
+
+    let error = t.harness.assert_error();
+    assert_eq!(error.context, "remove timer");
+    assert_eq!(error.to_string(), "Failed to find timer");
+    assert_eq!(t.harness.model().timers.len(), 1);
+}
+
+fn countdown(id: u32) -> Msg {
+    Msg::ModeChanged(id, value(Mode::Countdown))
+}
+
+fn time_is_up(title: &str) -> browser::Effect<Msg, AppEffect> {
+    browser::app_effect(AppEffect::Notify {
+        title: title.to_string(),
+        body: "Time's up".to_string(),
+    })
+}
+
+#[test]
+fn countdown_asks_for_notification_permission() {
+    let mut t = timers();
+
+    let request_permission = || browser::app_effect(AppEffect::RequestNotificationPermission);
+
+    t.at(0, countdown(1));
+
+    assert!(t.harness.has_effect(request_permission()));
+
+    // Only when switching to it
+    t.at(0, countdown(1));
+
+    assert!(!t.harness.has_effect(request_permission()));
+}
+
+#[test]
+fn countdown_shows_remaining_time() {
+    let mut t = timers();
+
+    t.at(0, countdown(1)).tick(4000);
+
+    assert_eq!(
+        t.timer(1).remaining(t.harness.model().current_time),
+        Duration::from_secs(11)
+    );
+    assert!(t.harness.contains_text("11.00s left"));
+    assert_eq!(
+        t.harness
+            .element(dom_id(Id::Elapsed))
+            .attr("value")
+            .unwrap(),
+        "11000"
+    );
+}
+
+#[test]
+fn countdown_notifies_once_when_it_reaches_zero() {
+    let mut t = timers();
+
+    t.at(0, countdown(1)).tick(14_900);
+    t.harness.assert_no_effects();
+
+    t.tick(15_100);
+    assert!(t.harness.has_effect(time_is_up("Timer 1")));
+    assert!(t.harness.contains_text("0.00s left"));
+
+    t.tick(15_300);
+    t.harness.assert_no_effects();
+}
+
+#[test]
+fn countdown_notifies_again_after_a_reset() {
+    let mut t = timers();
+
+    t.at(0, countdown(1))
+        .tick(15_000)
+        .at(16_000, Msg::ResetClicked(1))
+        .tick(31_000);
+
+    assert!(t.harness.has_effect(time_is_up("Timer 1")));
+}
+
+#[test]
+fn counting_up_finishes_silently() {
+    let mut t = timers();
+
+    t.tick(15_000);
+
+    t.harness.assert_no_effects();
+}
+
+#[test]
+fn invalid_mode_is_an_error() {
+    let mut t = timers();
+
+    t.at(0, Msg::ModeChanged(1, value("sideways")));
+
+    assert_eq!(t.harness.assert_error().context, "mode");
+    assert_eq!(t.timer(1).mode, Mode::CountUp);
+}
+
+// What the page saved after its latest message
+fn saved(t: &Timers) -> StoredTimers {
+    let model = t.harness.model();
+    StoredTimers::new(
+        model.current_time,
+        model.next_id,
+        model.slider_max,
+        model.timers.clone(),
+    )
+}
+
+// Opens the page again at the given time and loads what was saved
+fn reload(stored: &StoredTimers, millis: i128) -> Timers {
+    let mut t = timers();
+    let json = serde_json::to_string(stored).unwrap();
+
+    t.at(millis, Msg::TimersLoaded(Some(json)));
+    t
+}
+
+#[test]
+fn starts_by_loading_saved_timers() {
+    let t = timers();
+
+    t.harness
+        .assert_effects(vec![browser::app_effect(AppEffect::LoadTimers)]);
+}
+
+#[test]
+fn changes_are_saved() {
+    let mut t = timers();
+
+    t.at(1000, Msg::PauseClicked(1));
+
+    assert!(t
+        .harness
+        .has_effect(browser::app_effect(AppEffect::SaveTimers(saved(&t)))));
+
+    t.tick(2000);
+
+    t.harness.assert_no_effects();
+}
+
+#[test]
+fn reload_resumes_from_wall_clock_time() {
+    let mut t = timers();
+
+    t.at(0, Msg::AddTimerClicked)
+        .at(2000, Msg::LapClicked(1))
+        .at(3000, Msg::PauseClicked(2));
+
+    let t = reload(&saved(&t), 10_000);
+
+    assert_eq!(t.elapsed(1), Duration::from_secs(10));
+    assert_eq!(t.timer(1).laps, vec![Duration::from_secs(2)]);
+    assert_eq!(t.elapsed(2), Duration::from_secs(3));
+    assert!(t.timer(2).is_paused());
+    assert_eq!(t.harness.model().next_id, 3);
+}
+
+#[test]
+fn reload_after_the_clock_went_back_continues_from_the_saved_time() {
+    let mut t = timers();
+
+    t.at(5000, Msg::MaxDurationChanged(1, "20000".to_string()));
+
+    let mut t = reload(&saved(&t), 2000);
+
+    assert_eq!(t.elapsed(1), Duration::from_secs(5));
+
+    t.tick(3000);
+
+    assert_eq!(t.elapsed(1), Duration::from_secs(6));
+}
+
+#[test]
+fn reload_treats_a_future_start_as_starting_when_saved() {
+    let mut t = timers();
+
+    t.at(4000, Msg::AddTimerClicked);
+
+    let mut stored = saved(&t);
+    stored.timers[1].started_at = Some(Posix::from_millis(START + 60_000));
+
+    let t = reload(&stored, 5000);
+
+    assert_eq!(t.elapsed(2), Duration::from_secs(1));
+}
+
+#[test]
+fn countdown_that_ran_out_while_closed_notifies_on_reload() {
+    let mut t = timers();
+
+    t.at(0, countdown(1));
+
+    let t = reload(&saved(&t), 20_000);
+
+    assert!(t.harness.has_effect(time_is_up("Timer 1")));
+    assert!(t.harness.contains_text("0.00s left"));
+}
+
+#[test]
+fn countdown_that_already_notified_stays_quiet_on_reload() {
+    let mut t = timers();
+
+    t.at(0, countdown(1)).tick(15_000);
+
+    let t = reload(&saved(&t), 20_000);
+
+    assert!(!t.harness.has_effect(time_is_up("Timer 1")));
+}
+
+#[test]
+fn corrupt_storage_keeps_the_new_timer() {
+    let mut t = timers();
+
+    t.at(0, Msg::TimersLoaded(Some("not json".to_string())));
+
+    assert_eq!(t.harness.model().timers.len(), 1);
+    t.harness.assert_ok();
+}
+
+#[test]
+fn stored_timers_with_duplicate_ids_are_rejected() {
+    let mut t = timers();
+
+    t.at(0, Msg::AddTimerClicked);
+
+    let mut stored = saved(&t);
+    stored.timers[1].id = 1;
+    let json = serde_json::to_string(&stored).unwrap();
+
+    assert_eq!(
+        StoredTimers::decode(&json).err(),
+        Some("Stored timers contain duplicate ids".to_string())
+    );
+}
+
+#[test]
+fn stored_ids_without_a_successor_are_rejected() {
+    let mut t = timers();
+
+    let mut stored = saved(&t);
+    stored.timers[0].id = u32::MAX;
+    stored.timers[0].name = "Stored".to_string();
+    let json = serde_json::to_string(&stored).unwrap();
+
+    assert!(StoredTimers::decode(&json).is_err());
+
+    t.at(0, Msg::TimersLoaded(Some(json)));
+
+    t.harness.assert_ok();
+    assert_eq!(t.timer(1).name, "Timer 1");
+    assert_eq!(t.harness.model().next_id, 2);
+}
+
+#[test]
+fn adding_a_timer_fails_once_ids_run_out() {
+    let mut t = timers();
+
+    let mut stored = saved(&t);
+    stored.next_id = u32::MAX;
+    let json = serde_json::to_string(&stored).unwrap();
+
+    t.at(0, Msg::TimersLoaded(Some(json)))
+        .at(0, Msg::AddTimerClicked);
+
+    assert_eq!(t.harness.assert_error().context, "add timer");
+    assert_eq!(t.harness.model().timers.len(), 1);
+}
+
+#[test]
+fn typed_durations_set_the_slider() {
+    let mut t = timers();
+
+    t.at(0, Msg::DurationTextChanged(1, "1m30s".to_string()));
+
+    assert_eq!(t.timer(1).max_duration, Duration::from_secs(90));
+    assert_eq!(t.harness.element(dom_id(Id::Duration)).value(), "90000");
+    // The slider stretches to reach it
+    assert_eq!(
+        t.harness.element(dom_id(Id::Duration)).attr("max").unwrap(),
+        "90000"
+    );
+    assert!(t.harness.contains_text("0.00s / 01:30"));
+
+    t.at(0, Msg::DurationTextChanged(1, "00:00:20".to_string()));
+
+    assert_eq!(t.timer(1).max_duration, Duration::from_secs(20));
+}
+
+#[test]
+fn moving_the_slider_updates_the_text() {
+    let mut t = timers();
+
+    t.at(0, Msg::MaxDurationChanged(1, "22500".to_string()));
+
+    assert_eq!(t.harness.element(dom_id(Id::DurationText)).value(), "22.5s");
+}
+
+#[test]
+fn unfinished_duration_text_is_kept_and_marked_invalid() {
+    let mut t = timers();
+
+    t.at(0, Msg::DurationTextChanged(1, "1m3".to_string()));
+
+    let input = t.harness.element(dom_id(Id::DurationText));
+    assert_eq!(input.value(), "1m3");
+    assert!(input.is_invalid());
+    assert!(t.harness.contains_text("1m3 isn't a duration"));
+    assert_eq!(t.timer(1).max_duration, Duration::from_secs(15));
+    t.harness.assert_ok();
+
+    t.at(0, Msg::DurationTextChanged(1, "1m3s".to_string()));
+
+    assert!(!t.harness.element(dom_id(Id::DurationText)).is_invalid());
+    assert_eq!(t.timer(1).max_duration, Duration::from_secs(63));
+}
+
+#[test]
+fn slider_range_is_configurable() {
+    let mut t = timers();
+
+    assert_eq!(
+        t.harness.element(dom_id(Id::Duration)).attr("max").unwrap(),
+        "30000"
+    );
+
+    t.at(0, Msg::SliderMaxChanged("2h".to_string()));
+
+    assert_eq!(t.harness.model().slider_max, Duration::from_secs(7200));
+    assert_eq!(
+        t.harness.element(dom_id(Id::Duration)).attr("max").unwrap(),
+        "7200000"
+    );
+}
+
+#[test]
+fn zero_slider_range_is_rejected() {
+    let mut t = timers();
+
+    t.at(0, Msg::SliderMaxChanged("0s".to_string()));
+
+    assert_eq!(t.harness.model().slider_max, Duration::from_secs(30));
+    assert!(t.harness.element(Id::SliderMax).is_invalid());
+    assert!(t
+        .harness
+        .contains_text("The slider range must be longer than zero"));
+}
+
+#[test]
+fn long_timers_display_as_a_clock() {
+    let mut t = timers();
+
+    t.at(0, Msg::DurationTextChanged(1, "2h".to_string()))
+        .tick(3_725_000);
+
+    assert!(t.harness.contains_text("01:02:05 / 02:00:00"));
+
+    t.at(3_725_000, countdown(1));
+
+    assert!(t.harness.contains_text("57:55 left"));
+}
+
+#[test]
+fn reload_keeps_the_slider_range() {
+    let mut t = timers();
+
+    t.at(0, Msg::SliderMaxChanged("5m".to_string()));
+
+    let t = reload(&saved(&t), 1000);
+
+    assert_eq!(t.harness.model().slider_max, Duration::from_secs(300));
+    assert_eq!(t.harness.element(Id::SliderMax).value(), "5m");
+}
+
+#[test]
+fn invalid_duration_is_an_error() {
+    let mut t = timers();
+
+    t.at(0, Msg::MaxDurationChanged(1, "soon".to_string()));
+
+    let error = t.harness.assert_error();
+    assert_eq!(error.kind, ErrorKind::InvalidInput);
+    assert_eq!(error.context, "max duration");
+    assert_eq!(
+        error.to_string(),
+        "Failed to parse max duration: invalid digit found in string"
+    );
+
+    assert!(t.harness.exists(page_error::Id::PageError));
+
+    t.at(0, Msg::ErrorDismissed);
+
+    t.harness.assert_ok();
+    assert!(!t.harness.exists(page_error::Id::PageError));
+}
+
+#[test]
+fn failed_duration_change_leaves_the_timer_untouched() {
+    let mut t = timers();
+
+    t.tick(20_000);
+    let before = t.timer(1).clone();
+
+    t.at(21_000, Msg::MaxDurationChanged(1, "soon".to_string()));
+
+    t.harness.assert_error();
+    assert_eq!(t.timer(1).accumulated, before.accumulated);
+    assert_eq!(t.timer(1).started_at, before.started_at);
+    assert_eq!(t.timer(1).max_duration, Duration::from_secs(15));
+
+    t.at(21_000, Msg::MaxDurationChanged(1, "25000".to_string()))
+        .tick(22_000);
+
+    assert_eq!(t.elapsed(1), Duration::from_secs(16));
+}
+
+#[test]
+fn failed_updates_change_nothing() {
+    let mut t = timers();
+
+    t.tick(1000).at(2000, Msg::PauseClicked(1)).tick(3000);
+    t.harness.assert_ok();
+    let before = serde_json::to_value(t.harness.model()).unwrap();
+
+    for msg in [
+        Msg::PauseClicked(9),
+        Msg::PauseClicked(1),
+        Msg::MaxDurationChanged(1, "soon".to_string()),
+        Msg::ModeChanged(1, value("sideways")),
+        Msg::LapClicked(1),
+        Msg::ExportLapsClicked(1),
+        Msg::OnTick(value("not a time")),
+    ] {
+        t.at(5000, msg);
+        t.harness.assert_error();
+
+        let mut after = serde_json::to_value(t.harness.model()).unwrap();
+        after["error"] = serde_json::Value::Null;
+        assert_eq!(after, before);
+    }
+}
+
+#[test]
+fn changes_before_a_failure_are_rolled_back() {
+    let mut t = timers();
+
+    t.tick(1000).at(2000, Msg::AddTimerClicked);
+    let mut model = t.harness.model().clone();
+    let before = serde_json::to_value(&model).unwrap();
+
+    let result = runtime::update::<Model, Msg, AppEffect>(&mut model, |model| {
+        model.timers.retain(|timer| timer.id != 1);
+        model.timers[0].name = "Renamed".to_string();
+        model.next_id = 99;
+        model.slider_max = Duration::from_secs(1);
+        Err(PageError::invalid_state("remove timer", "nope"))
+    });
+
+    assert!(result.unwrap().is_empty());
+    assert_eq!(
+        model.error,
+        Some(PageError::invalid_state("remove timer", "nope"))
+    );
+
+    let mut after = serde_json::to_value(&model).unwrap();
+    after["error"] = serde_json::Value::Null;
+    assert_eq!(after, before);
+}
+
+const MINUTE: i128 = 60_000;
+
+#[derive(Debug, PartialEq)]
+struct Activity {
+    updates: usize,
+    renders: usize,
+}
+
+fn measure(t: &mut Timers, run: impl FnOnce(&mut Timers)) -> Activity {
+    let (updates, renders) = (t.harness.updates(), t.harness.renders());
+
+    run(t);
+
+    Activity {
+        updates: t.harness.updates() - updates,
+        renders: t.harness.renders() - renders,
+    }
+}
+
+// Follows the page's tick interval for a while
+fn run_for(t: &mut Timers, from: i128, millis: i128) {
+    let mut now = from;
+
+    while let Some(interval) = timer_page::tick_interval(t.harness.model()) {
+        now += interval.as_millis() as i128;
+
+        if now > from + millis {
+            break;
+        }
+
+        t.tick(now);
+    }
+}
+
+// The subscriptions the page started out with: the time every 100ms no matter what, and a 200ms tick
+// until the timer was full. Both arrive as ticks here, since all the time message did was store it.
+fn poll_like_the_baseline(t: &mut Timers, from: i128, millis: i128) {
+    for now in (from + 100..=from + millis).step_by(100) {
+        let model = t.harness.model();
+        let filling = model
+            .timers
+            .iter()
+            .any(|timer| !timer.is_finished(model.current_time));
+
+        t.tick(now);
+
+        if now % 200 == 0 && filling {
+            t.tick(now);
+        }
+    }
+}
+
+#[test]
+fn baseline_polling_updated_a_full_timer_all_minute() {
+    let mut t = timers();
+
+    let activity = measure(&mut t, |t| poll_like_the_baseline(t, 0, MINUTE));
+
+    assert_eq!(
+        activity,
+        Activity {
+            updates: 675,
+            renders: 675
+        }
+    );
+    assert_eq!(t.elapsed(1), Duration::from_secs(15));
+}
+
+#[test]
+fn ticks_stop_once_the_timer_is_full() {
+    let mut t = timers();
+
+    let activity = measure(&mut t, |t| run_for(t, 0, MINUTE));
+
+    assert_eq!(
+        activity,
+        Activity {
+            updates: 75,
+            renders: 75
+        }
+    );
+    assert_eq!(t.elapsed(1), Duration::from_secs(15));
+    assert_eq!(timer_page::tick_interval(t.harness.model()), None);
+}
+
+#[test]
+fn paused_timers_need_no_ticks() {
+    let mut t = timers();
+
+    t.at(0, Msg::PauseClicked(1));
+
+    let activity = measure(&mut t, |t| run_for(t, 0, MINUTE));
+
+    assert_eq!(
+        activity,
+        Activity {
+            updates: 0,
+            renders: 0
+        }
+    );
+}
+
+#[test]
+fn hidden_tab_stops_ticking_and_catches_up_when_shown() {
+    let mut t = timers();
+
+    t.at(0, Msg::VisibilityChanged(false));
+
+    let activity = measure(&mut t, |t| run_for(t, 0, MINUTE));
+
+    assert_eq!(
+        activity,
+        Activity {
+            updates: 0,
+            renders: 0
+        }
+    );
+
+    t.at(10_000, Msg::VisibilityChanged(true));
+    assert_eq!(t.elapsed(1), Duration::from_secs(10));
+    assert_eq!(
+        timer_page::tick_interval(t.harness.model()),
+        Some(Duration::from_millis(200))
+    );
+}
+
+#[test]
+fn hidden_countdown_ticks_slowly_until_it_runs_out() {
+    let mut t = timers();
+
+    t.at(0, countdown(1)).at(0, Msg::VisibilityChanged(false));
+
+    let activity = measure(&mut t, |t| run_for(t, 0, MINUTE));
+
+    assert_eq!(
+        activity,
+        Activity {
+            updates: 15,
+            renders: 15
+        }
+    );
+    assert!(t.harness.has_effect(time_is_up("Timer 1")));
+    assert_eq!(timer_page::tick_interval(t.harness.model()), None);
+}
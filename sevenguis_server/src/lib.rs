@@ -0,0 +1,441 @@
+use sevenguis_core::clock::Clock;
+use sevenguis_core::clock::SystemClock;
+use sevenguis_core::registry;
+use std::fs;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::net::Shutdown;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+// Applies to the request line and to each header line, line break included
+const MAX_LINE_LENGTH: usize = 8 * 1024;
+const MAX_HEADERS: usize = 100;
+const DEFAULT_MAX_CONNECTIONS: usize = 128;
+
+pub struct Server {
+    listener: TcpListener,
+    static_dir: Arc<PathBuf>,
+    timeout: Duration,
+    max_connections: usize,
+    active_connections: Arc<AtomicUsize>,
+}
+
+impl Server {
+    pub fn bind(addr: &str, static_dir: impl Into<PathBuf>) -> Result<Server, String> {
+        let listener =
+            TcpListener::bind(addr).map_err(|err| format!("Failed to bind {}: {}", addr, err))?;
+
+        Ok(Server {
+            listener,
+            static_dir: Arc::new(static_dir.into()),
+            timeout: DEFAULT_TIMEOUT,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            active_connections: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    // How long a client gets to send its request, and to take the response
+    pub fn timeout(self, timeout: Duration) -> Server {
+        Server { timeout, ..self }
+    }
+
+    // How many connections are handled at once, the rest are turned away
+    pub fn max_connections(self, max_connections: usize) -> Server {
+        Server {
+            max_connections,
+            ..self
+        }
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, String> {
+        self.listener
+            .local_addr()
+            .map_err(|err| format!("Failed to get local address: {}", err))
+    }
+
+    // Handles each connection on its own thread, forever
+    pub fn run(self) {
+        for stream in self.listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let slot = match ConnectionSlot::take(
+                        &self.active_connections,
+                        self.max_connections,
+                    ) {
+                        Some(slot) => slot,
+                        None => {
+                            reject_connection(stream);
+                            continue;
+                        }
+                    };
+
+                    let static_dir = Arc::clone(&self.static_dir);
+                    let timeout = self.timeout;
+
+                    thread::spawn(move || {
+                        if let Err(err) = handle_connection(stream, &static_dir, timeout) {
+                            eprintln!("Failed to handle request: {}", err);
+                        }
+
+                        drop(slot);
+                    });
+                }
+
+                Err(err) => {
+                    eprintln!("Failed to accept connection: {}", err);
+                }
+            }
+        }
+    }
+}
+
+// Counts a connection as active until it's dropped
+struct ConnectionSlot {
+    active: Arc<AtomicUsize>,
+}
+
+impl ConnectionSlot {
+    fn take(active: &Arc<AtomicUsize>, max: usize) -> Option<ConnectionSlot> {
+        let previous = active.fetch_add(1, Ordering::SeqCst);
+        // Over the limit the slot is dropped right away, which takes the count back down
+        let slot = ConnectionSlot {
+            active: Arc::clone(active),
+        };
+
+        if previous < max {
+            Some(slot)
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// Answers on the accepting thread without blocking it, a client that isn't reading just gets dropped
+fn reject_connection(stream: TcpStream) {
+    let response = Response::text("503 Service Unavailable", "Too many connections");
+
+    if stream.set_nonblocking(true).is_ok() {
+        let _ = write_response(&stream, &response, false);
+    }
+}
+
+// Renders the page for a route, time dependent pages start from the clock's current time
+pub fn render_route(path: &str, clock: Rc<dyn Clock>) -> Option<Result<String, String>> {
+    let page = registry::find_by_route(path)?;
+    Some((page.new)(clock).render(None))
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn new(status: &'static str, content_type: &'static str, body: Vec<u8>) -> Response {
+        Response {
+            status,
+            content_type,
+            headers: vec![],
+            body,
+        }
+    }
+
+    fn text(status: &'static str, body: &str) -> Response {
+        Response::new(
+            status,
+            "text/plain; charset=utf-8",
+            body.as_bytes().to_vec(),
+        )
+    }
+
+    fn redirect(location: String) -> Response {
+        Response {
+            headers: vec![("Location", location)],
+            ..Response::text("301 Moved Permanently", "")
+        }
+    }
+}
+
+struct Request {
+    method: String,
+    target: String,
+}
+
+enum ReadError {
+    Timeout,
+    UriTooLong,
+    HeadersTooLarge,
+    Failed(io::Error),
+}
+
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> ReadError {
+        match err.kind() {
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => ReadError::Timeout,
+            _ => ReadError::Failed(err),
+        }
+    }
+}
+
+// Gives the whole request one deadline, so a client sending a byte at a time can't hold on to a thread
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+
+        if remaining.is_zero() {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "Request took too long",
+            ));
+        }
+
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    static_dir: &Path,
+    timeout: Duration,
+) -> Result<(), String> {
+    stream
+        .set_write_timeout(Some(timeout))
+        .map_err(|err| format!("Failed to set write timeout: {}", err))?;
+
+    let mut reader = BufReader::new(DeadlineReader {
+        stream: &stream,
+        deadline: Instant::now() + timeout,
+    });
+
+    let (response, head_only) = match read_request(&mut reader) {
+        Ok(request) => {
+            let response = match request.method.as_str() {
+                "GET" | "HEAD" => respond(&request.target, static_dir),
+                _ => Response::text("405 Method Not Allowed", "Method not allowed"),
+            };
+
+            (response, request.method == "HEAD")
+        }
+
+        Err(ReadError::Timeout) => (
+            Response::text("408 Request Timeout", "Request timeout"),
+            false,
+        ),
+
+        Err(ReadError::UriTooLong) => (Response::text("414 URI Too Long", "URI too long"), false),
+
+        Err(ReadError::HeadersTooLarge) => (
+            Response::text(
+                "431 Request Header Fields Too Large",
+                "Request header fields too large",
+            ),
+            false,
+        ),
+
+        Err(ReadError::Failed(err)) => {
+            return Err(format!("Failed to read request: {}", err));
+        }
+    };
+
+    write_response(&stream, &response, head_only)
+        .map_err(|err| format!("Failed to write response: {}", err))?;
+
+    // Closing with unread input resets the connection, which can lose the response on the way out
+    let _ = stream.shutdown(Shutdown::Write);
+    let _ = io::copy(&mut reader.take(MAX_LINE_LENGTH as u64), &mut io::sink());
+
+    Ok(())
+}
+
+fn read_request(reader: &mut impl BufRead) -> Result<Request, ReadError> {
+    let request_line = read_line(reader)?.ok_or(ReadError::UriTooLong)?;
+
+    // Headers are not used, but must be read before responding
+    for _ in 0..=MAX_HEADERS {
+        let line = read_line(reader)?.ok_or(ReadError::HeadersTooLarge)?;
+
+        if line.trim().is_empty() {
+            let mut parts = request_line.split_whitespace();
+
+            return Ok(Request {
+                method: parts.next().unwrap_or_default().to_string(),
+                target: parts.next().unwrap_or_default().to_string(),
+            });
+        }
+    }
+
+    Err(ReadError::HeadersTooLarge)
+}
+
+// Reads a line, or None when it's longer than MAX_LINE_LENGTH. The end of input reads as an empty line.
+fn read_line(reader: &mut impl BufRead) -> Result<Option<String>, ReadError> {
+    let mut line = vec![];
+
+    reader
+        .take(MAX_LINE_LENGTH as u64 + 1)
+        .read_until(b'\n', &mut line)?;
+
+    if line.len() > MAX_LINE_LENGTH {
+        Ok(None)
+    } else {
+        Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+    }
+}
+
+fn respond(target: &str, static_dir: &Path) -> Response {
+    let raw_path = target.split(['?', '#']).next().unwrap_or_default();
+
+    let path = match percent_decode(raw_path) {
+        Some(path) => path,
+        None => return Response::text("400 Bad Request", "Bad request"),
+    };
+    let path = path.as_str();
+
+    match render_route(path, Rc::new(SystemClock)) {
+        Some(Ok(html)) => {
+            return Response {
+                headers: vec![("Cache-Control", "no-store".to_string())],
+                ..Response::new("200 OK", "text/html; charset=utf-8", html.into_bytes())
+            };
+        }
+
+        Some(Err(err)) => {
+            eprintln!("Failed to render {}: {}", path, err);
+            return Response::text("500 Internal Server Error", "Failed to render page");
+        }
+
+        None => {}
+    }
+
+    let with_slash = format!("{}/", path);
+    if registry::find_by_route(&with_slash).is_some() {
+        return Response::redirect(with_slash);
+    }
+
+    match static_file(static_dir, path) {
+        Some(file) => {
+            let content_type = content_type(&file);
+
+            match fs::read(&file) {
+                Ok(body) => Response::new("200 OK", content_type, body),
+                Err(_) => Response::text("404 Not Found", "Not found"),
+            }
+        }
+
+        None => Response::text("404 Not Found", "Not found"),
+    }
+}
+
+// Decodes %XX escapes, None when an escape is malformed or the result isn't utf-8
+fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = bytes.get(index + 1..index + 3)?;
+
+            if !hex.iter().all(|b| b.is_ascii_hexdigit()) {
+                return None;
+            }
+
+            let hex = std::str::from_utf8(hex).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+// Maps a request path to a file inside the static dir, refusing anything that could escape it
+fn static_file(static_dir: &Path, path: &str) -> Option<PathBuf> {
+    let relative = Path::new(path.trim_start_matches('/'));
+
+    let is_safe = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+
+    if !is_safe || path.contains('\\') {
+        return None;
+    }
+
+    let file = static_dir.join(relative);
+
+    if file.is_dir() {
+        Some(file.join("index.html")).filter(|file| file.is_file())
+    } else {
+        Some(file).filter(|file| file.is_file())
+    }
+}
+
+fn content_type(file: &Path) -> &'static str {
+    match file.extension().and_then(|ext| ext.to_str()) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js") => "text/javascript; charset=utf-8",
+        Some("wasm") => "application/wasm",
+        Some("json") => "application/json",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("ico") => "image/x-icon",
+        _ => "application/octet-stream",
+    }
+}
+
+fn write_response(mut stream: &TcpStream, response: &Response, head_only: bool) -> io::Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    );
+
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+
+    head.push_str("\r\n");
+
+    stream.write_all(head.as_bytes())?;
+
+    if !head_only {
+        stream.write_all(&response.body)?;
+    }
+
+    stream.flush()
+}
@@ -0,0 +1,36 @@
+use sevenguis_server::Server;
+use std::env;
+use std::process;
+
+const DEFAULT_ADDR: &str = "127.0.0.1:8003";
+const DEFAULT_STATIC_DIR: &str = "dist";
+
+fn main() {
+    let args_: Vec<String> = env::args().collect();
+    let args: Vec<&str> = args_.iter().map(|s| s.as_ref()).collect();
+
+    let (addr, static_dir) = match args[1..] {
+        [] => (DEFAULT_ADDR, DEFAULT_STATIC_DIR),
+        ["--addr", addr] => (addr, DEFAULT_STATIC_DIR),
+        ["--static", static_dir] => (DEFAULT_ADDR, static_dir),
+        ["--addr", addr, "--static", static_dir] | ["--static", static_dir, "--addr", addr] => {
+            (addr, static_dir)
+        }
+
+        _ => {
+            eprintln!("Usage: sevenguis_server [--addr <host:port>] [--static <dir>]");
+            process::exit(1);
+        }
+    };
+
+    let server = match Server::bind(addr, static_dir) {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+
+    println!("Serving {} on http://{}", static_dir, addr);
+    server.run();
+}
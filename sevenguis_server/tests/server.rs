@@ -0,0 +1,268 @@
+use polyester::time::Posix;
+use sevenguis_core::clock::ManualClock;
+use sevenguis_core::clock::SystemClock;
+use sevenguis_server::Server;
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::net::TcpStream;
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+
+struct Response {
+    status: String,
+    headers: String,
+    body: String,
+}
+
+fn start_server(name: &str) -> SocketAddr {
+    start_server_with_timeout(name, Duration::from_secs(10))
+}
+
+// Starts a server on a free localhost port, serving a fresh static dir
+fn start_server_with_timeout(name: &str, timeout: Duration) -> SocketAddr {
+    start_configured_server(name, |server| server.timeout(timeout))
+}
+
+fn start_configured_server(name: &str, configure: impl FnOnce(Server) -> Server) -> SocketAddr {
+    let static_dir = env::temp_dir().join(format!("sevenguis_server_{}", name));
+    let _ = fs::remove_dir_all(&static_dir);
+    fs::create_dir_all(static_dir.join("assets")).unwrap();
+    fs::write(static_dir.join("app.css"), "body { margin: 0; }").unwrap();
+    fs::write(static_dir.join("assets").join("index.html"), "assets").unwrap();
+    fs::write(static_dir.join("with space.css"), "spaced").unwrap();
+
+    let server = configure(Server::bind("127.0.0.1:0", static_dir).unwrap());
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+
+    addr
+}
+
+// Sends raw bytes and returns the status line of the response
+fn send_raw(addr: SocketAddr, raw: &[u8]) -> String {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(raw).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    response.lines().next().unwrap_or_default().to_string()
+}
+
+fn request(addr: SocketAddr, method: &str, path: &str) -> Response {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\n\r\n",
+        method, path
+    )
+    .unwrap();
+
+    let mut raw = String::new();
+    stream.read_to_string(&mut raw).unwrap();
+
+    let (head, body) = raw.split_once("\r\n\r\n").unwrap();
+    let (status_line, headers) = head.split_once("\r\n").unwrap_or((head, ""));
+
+    Response {
+        status: status_line.split_once(' ').unwrap().1.to_string(),
+        headers: headers.to_lowercase(),
+        body: body.to_string(),
+    }
+}
+
+#[test]
+fn renders_every_page() {
+    let addr = start_server("pages");
+
+    for path in [
+        "/",
+        "/counter/",
+        "/temperature/",
+        "/timer/",
+        "/crud/",
+        "/flight/",
+        "/circle/",
+        "/cells/",
+    ] {
+        let response = request(addr, "GET", path);
+
+        assert_eq!(response.status, "200 OK", "{}", path);
+        assert!(response.headers.contains("content-type: text/html"));
+        assert!(response.body.contains("id=\"sevenguis\""), "{}", path);
+    }
+}
+
+#[test]
+fn renders_with_the_current_time() {
+    let addr = start_server("time");
+
+    let before = sevenguis_server::render_route("/flight/", Rc::new(SystemClock))
+        .unwrap()
+        .ok();
+    let response = request(addr, "GET", "/flight/?source=test");
+    let after = sevenguis_server::render_route("/flight/", Rc::new(SystemClock))
+        .unwrap()
+        .ok();
+
+    // Either render could straddle midnight, but not both
+    assert!(Some(&response.body) == before.as_ref() || Some(&response.body) == after.as_ref());
+    assert!(response.headers.contains("cache-control: no-store"));
+}
+
+#[test]
+fn flight_page_starts_on_the_given_day() {
+    // 2022-10-01T12:00:00Z
+    let clock = ManualClock::new(Posix::from_millis(1_664_625_600_000));
+    let html = sevenguis_server::render_route("/flight/", Rc::new(clock));
+
+    assert!(html.unwrap().unwrap().contains("value=\"2022-10-01\""));
+}
+
+#[test]
+fn redirects_routes_without_trailing_slash() {
+    let addr = start_server("redirect");
+
+    let response = request(addr, "GET", "/timer");
+
+    assert_eq!(response.status, "301 Moved Permanently");
+    assert!(response.headers.contains("location: /timer/"));
+}
+
+#[test]
+fn serves_static_assets() {
+    let addr = start_server("static");
+
+    let response = request(addr, "GET", "/app.css");
+    assert_eq!(response.status, "200 OK");
+    assert!(response.headers.contains("content-type: text/css"));
+    assert_eq!(response.body, "body { margin: 0; }");
+
+    let response = request(addr, "GET", "/assets/");
+    assert_eq!(response.body, "assets");
+
+    let response = request(addr, "HEAD", "/app.css");
+    assert!(response.headers.contains("content-length: 19"));
+    assert_eq!(response.body, "");
+}
+
+#[test]
+fn rejects_unknown_paths_and_methods() {
+    let addr = start_server("errors");
+
+    assert_eq!(request(addr, "GET", "/missing.js").status, "404 Not Found");
+    assert_eq!(
+        request(addr, "GET", "/../Cargo.toml").status,
+        "404 Not Found"
+    );
+    assert_eq!(
+        request(addr, "POST", "/counter/").status,
+        "405 Method Not Allowed"
+    );
+}
+
+#[test]
+fn decodes_escaped_paths() {
+    let addr = start_server("escapes");
+
+    assert_eq!(request(addr, "GET", "/circle%2F").status, "200 OK");
+    assert_eq!(request(addr, "GET", "/app%2Ecss").status, "200 OK");
+    assert_eq!(request(addr, "GET", "/with%20space.css").body, "spaced");
+    assert_eq!(
+        request(addr, "GET", "/%2e%2e/Cargo.toml").status,
+        "404 Not Found"
+    );
+    assert_eq!(request(addr, "GET", "/app%zzcss").status, "400 Bad Request");
+    assert_eq!(request(addr, "GET", "/app%E2%82").status, "400 Bad Request");
+}
+
+#[test]
+fn slow_clients_time_out() {
+    let addr = start_server_with_timeout("slow", Duration::from_millis(200));
+
+    assert_eq!(send_raw(addr, b""), "HTTP/1.1 408 Request Timeout");
+    assert_eq!(
+        send_raw(addr, b"GET / HTTP/1.1\r\nHost: localhost\r\n"),
+        "HTTP/1.1 408 Request Timeout"
+    );
+
+    // A byte at a time, never finishing, still runs out of time
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(b"GET / HTTP/1.1\r\n").unwrap();
+    let mut writer = stream.try_clone().unwrap();
+    thread::spawn(move || {
+        for _ in 0..20 {
+            thread::sleep(Duration::from_millis(50));
+
+            if writer.write_all(b"x").is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut response = String::new();
+    let _ = stream.read_to_string(&mut response);
+    assert!(response.starts_with("HTTP/1.1 408 Request Timeout"));
+}
+
+#[test]
+fn connections_over_the_limit_are_turned_away() {
+    let addr = start_configured_server("busy", |server| server.max_connections(1));
+
+    // Holds the only slot until it's dropped
+    let idle = TcpStream::connect(addr).unwrap();
+
+    let mut turned_away = TcpStream::connect(addr).unwrap();
+    let mut response = String::new();
+    turned_away.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 503 Service Unavailable"));
+
+    drop(idle);
+
+    // The slot is given back once the idle connection has been handled. Turned away requests
+    // can be reset before their response is read, so those are just retried.
+    let served = (0..50).any(|_| {
+        thread::sleep(Duration::from_millis(20));
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        let mut response = String::new();
+
+        stream.write_all(b"GET / HTTP/1.1\r\n\r\n").is_ok()
+            && stream.read_to_string(&mut response).is_ok()
+            && response.starts_with("HTTP/1.1 200 OK")
+    });
+    assert!(served);
+}
+
+#[test]
+fn oversized_requests_are_rejected() {
+    let addr = start_server("oversized");
+    let long = "a".repeat(9000);
+
+    assert_eq!(
+        send_raw(addr, format!("GET /{} HTTP/1.1\r\n\r\n", long).as_bytes()),
+        "HTTP/1.1 414 URI Too Long"
+    );
+    assert_eq!(
+        send_raw(
+            addr,
+            format!("GET / HTTP/1.1\r\nX-Long: {}\r\n\r\n", long).as_bytes()
+        ),
+        "HTTP/1.1 431 Request Header Fields Too Large"
+    );
+
+    let headers = |count: usize| {
+        let headers = "X-Header: 1\r\n".repeat(count);
+        format!("GET / HTTP/1.1\r\n{}\r\n", headers)
+    };
+
+    assert_eq!(send_raw(addr, headers(100).as_bytes()), "HTTP/1.1 200 OK");
+    assert_eq!(
+        send_raw(addr, headers(101).as_bytes()),
+        "HTTP/1.1 431 Request Header Fields Too Large"
+    );
+}
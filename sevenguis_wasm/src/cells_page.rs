@@ -0,0 +1,16 @@
+use polyester::page::wasm;
+use polyester::page::Page;
+use polyester_macro::impl_wasm_page;
+use sevenguis_core::cells_page;
+use sevenguis_core::hydration::Hydrated;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct CellsPage(Hydrated<cells_page::CellsPage>);
+
+impl_wasm_page!(CellsPage);
+
+#[wasm_bindgen(js_name = cellsPage)]
+pub fn cells_page(model: Option<String>) -> Result<CellsPage, JsValue> {
+    Ok(CellsPage(Hydrated::new(cells_page::CellsPage {}, model)))
+}
@@ -0,0 +1,16 @@
+use polyester::page::wasm;
+use polyester::page::Page;
+use polyester_macro::impl_wasm_page;
+use sevenguis_core::circle_page;
+use sevenguis_core::hydration::Hydrated;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct CirclePage(Hydrated<circle_page::CirclePage>);
+
+impl_wasm_page!(CirclePage);
+
+#[wasm_bindgen(js_name = circlePage)]
+pub fn circle_page(model: Option<String>) -> Result<CirclePage, JsValue> {
+    Ok(CirclePage(Hydrated::new(circle_page::CirclePage {}, model)))
+}
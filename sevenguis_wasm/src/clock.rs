@@ -0,0 +1,18 @@
+use polyester::time;
+use sevenguis_core::clock::Clock;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = Date, js_name = now)]
+    fn date_now() -> f64;
+}
+
+// Reads the browser's time whenever it's asked
+pub struct JsClock;
+
+impl Clock for JsClock {
+    fn now(&self) -> time::Posix {
+        time::Posix::from_millis(date_now() as i128)
+    }
+}
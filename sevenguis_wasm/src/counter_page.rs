@@ -2,14 +2,18 @@ use polyester::page::wasm;
 use polyester::page::Page;
 use polyester_macro::impl_wasm_page;
 use sevenguis_core::counter_page;
+use sevenguis_core::hydration::Hydrated;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
-pub struct CounterPage(counter_page::CounterPage);
+pub struct CounterPage(Hydrated<counter_page::CounterPage>);
 
 impl_wasm_page!(CounterPage);
 
 #[wasm_bindgen(js_name = counterPage)]
-pub fn counter_page() -> Result<CounterPage, JsValue> {
-    Ok(CounterPage(counter_page::CounterPage {}))
+pub fn counter_page(model: Option<String>) -> Result<CounterPage, JsValue> {
+    Ok(CounterPage(Hydrated::new(
+        counter_page::CounterPage {},
+        model,
+    )))
 }
@@ -2,14 +2,15 @@ use polyester::page::wasm;
 use polyester::page::Page;
 use polyester_macro::impl_wasm_page;
 use sevenguis_core::crud_page;
+use sevenguis_core::hydration::Hydrated;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
-pub struct CrudPage(crud_page::CrudPage);
+pub struct CrudPage(Hydrated<crud_page::CrudPage>);
 
 impl_wasm_page!(CrudPage);
 
 #[wasm_bindgen(js_name = crudPage)]
-pub fn crud_page() -> Result<CrudPage, JsValue> {
-    Ok(CrudPage(crud_page::CrudPage {}))
+pub fn crud_page(model: Option<String>) -> Result<CrudPage, JsValue> {
+    Ok(CrudPage(Hydrated::new(crud_page::CrudPage {}, model)))
 }
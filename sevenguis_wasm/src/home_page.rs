@@ -2,14 +2,15 @@ use polyester::page::wasm;
 use polyester::page::Page;
 use polyester_macro::impl_wasm_page;
 use sevenguis_core::home_page;
+use sevenguis_core::hydration::Hydrated;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
-pub struct HomePage(home_page::HomePage);
+pub struct HomePage(Hydrated<home_page::HomePage>);
 
 impl_wasm_page!(HomePage);
 
 #[wasm_bindgen(js_name = homePage)]
-pub fn home_page() -> Result<HomePage, JsValue> {
-    Ok(HomePage(home_page::HomePage {}))
+pub fn home_page(model: Option<String>) -> Result<HomePage, JsValue> {
+    Ok(HomePage(Hydrated::new(home_page::HomePage {}, model)))
 }
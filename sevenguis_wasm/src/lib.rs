@@ -1,3 +1,6 @@
+pub mod cells_page;
+pub mod circle_page;
+pub mod clock;
 pub mod counter_page;
 pub mod crud_page;
 pub mod flight_page;
@@ -1,15 +1,19 @@
 use polyester::page::wasm;
 use polyester::page::Page;
 use polyester_macro::impl_wasm_page;
+use sevenguis_core::hydration::Hydrated;
 use sevenguis_core::temperature_page;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
-pub struct TemperaturePage(temperature_page::TemperaturePage);
+pub struct TemperaturePage(Hydrated<temperature_page::TemperaturePage>);
 
 impl_wasm_page!(TemperaturePage);
 
 #[wasm_bindgen(js_name = temperaturePage)]
-pub fn temperature_page() -> Result<TemperaturePage, JsValue> {
-    Ok(TemperaturePage(temperature_page::TemperaturePage {}))
+pub fn temperature_page(model: Option<String>) -> Result<TemperaturePage, JsValue> {
+    Ok(TemperaturePage(Hydrated::new(
+        temperature_page::TemperaturePage {},
+        model,
+    )))
 }
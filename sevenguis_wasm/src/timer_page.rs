@@ -1,21 +1,23 @@
+use crate::clock::JsClock;
 use polyester::page::wasm;
 use polyester::page::Page;
 use polyester_macro::impl_wasm_page;
+use sevenguis_core::hydration::Hydrated;
 use sevenguis_core::timer_page;
+use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
-pub struct TimerPage(timer_page::TimerPage);
+pub struct TimerPage(Hydrated<timer_page::TimerPage>);
 
 impl_wasm_page!(TimerPage);
 
 #[wasm_bindgen(js_name = timerPage)]
-pub fn timer_page(js_current_time: &JsValue) -> Result<TimerPage, JsValue> {
-    let current_time = js_current_time
-        .into_serde()
-        .map_err(|err| format!("Failed to decode current time: {}", err))?;
-
-    Ok(TimerPage(timer_page::TimerPage {
-        initial_time: current_time,
-    }))
+pub fn timer_page(model: Option<String>) -> Result<TimerPage, JsValue> {
+    Ok(TimerPage(Hydrated::new(
+        timer_page::TimerPage {
+            clock: Rc::new(JsClock),
+        },
+        model,
+    )))
 }
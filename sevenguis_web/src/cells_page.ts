@@ -0,0 +1,29 @@
+import init, { cellsPage } from "../wasm/sevenguis.js";
+import { Polyester } from "polyester";
+import { defaultDebugConfig } from "polyester/src/logger";
+import { embeddedModel } from "./hydration";
+
+(async () => {
+  await init("/wasm/sevenguis_bg.wasm");
+
+  const polyester = new Polyester(cellsPage(embeddedModel()), {
+    loggerConfig: defaultDebugConfig(),
+  });
+
+  polyester.init();
+
+  document.addEventListener("dblclick", (event) => {
+    const target = event.target as Element;
+    const cell = target.closest("[data-cell]");
+    if (cell) {
+      polyester.sendMessage("editStarted", cell.getAttribute("data-cell"));
+    }
+  });
+
+  document.addEventListener("keydown", (event) => {
+    const target = event.target as Element;
+    if (target.id === "editor" && event.key === "Escape") {
+      polyester.sendMessage("editCancelled");
+    }
+  });
+})();
@@ -0,0 +1,61 @@
+import init, { circlePage } from "../wasm/sevenguis.js";
+import { Polyester } from "polyester";
+import { defaultDebugConfig } from "polyester/src/logger";
+import { embeddedModel } from "./hydration";
+
+(async () => {
+  await init("/wasm/sevenguis_bg.wasm");
+
+  const polyester = new Polyester(circlePage(embeddedModel()), {
+    loggerConfig: defaultDebugConfig(),
+  });
+
+  polyester.init();
+
+  // The canvas is re-rendered on every update, so listen on the document
+  // and translate pointer events into canvas coordinates.
+  const canvasPoint = (event: MouseEvent) => {
+    const target = event.target as Element;
+    const canvas = target.closest("#canvas");
+    if (!canvas) {
+      return null;
+    }
+
+    const rect = canvas.getBoundingClientRect();
+
+    return {
+      x: Math.round(event.clientX - rect.left),
+      y: Math.round(event.clientY - rect.top),
+    };
+  };
+
+  document.addEventListener("click", (event) => {
+    const point = canvasPoint(event);
+    if (point) {
+      polyester.sendMessage("canvasClicked", point);
+    }
+  });
+
+  document.addEventListener("contextmenu", (event) => {
+    const point = canvasPoint(event);
+    if (point) {
+      event.preventDefault();
+      polyester.sendMessage("canvasRightClicked", point);
+    }
+  });
+
+  document.addEventListener("mousemove", (event) => {
+    const point = canvasPoint(event);
+    if (point) {
+      polyester.sendMessage("pointerMoved", point);
+    }
+  });
+
+  document.addEventListener("mouseout", (event) => {
+    const target = event.target as Element;
+    const related = event.relatedTarget as Element | null;
+    if (target.id === "canvas" && !related?.closest("#canvas")) {
+      polyester.sendMessage("pointerLeft");
+    }
+  });
+})();
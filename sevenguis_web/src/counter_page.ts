@@ -2,13 +2,16 @@ import init from "../wasm/sevenguis.js";
 import { counterPage } from "../wasm/sevenguis";
 import { Polyester } from "polyester";
 import { defaultDebugConfig } from "polyester/src/logger";
+import { bindUndoRedo } from "./history";
+import { embeddedModel } from "./hydration";
 
 (async () => {
   await init("/wasm/sevenguis_bg.wasm");
 
-  const polyester = new Polyester(counterPage(), {
+  const polyester = new Polyester(counterPage(embeddedModel()), {
     loggerConfig: defaultDebugConfig(),
   });
 
   polyester.init();
+  bindUndoRedo(polyester);
 })();
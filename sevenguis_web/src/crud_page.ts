@@ -2,13 +2,43 @@ import init from "../wasm/sevenguis.js";
 import { crudPage } from "../wasm/sevenguis";
 import { Polyester } from "polyester";
 import { defaultDebugConfig } from "polyester/src/logger";
+import { download } from "./download";
+import { bindUndoRedo } from "./history";
+import { embeddedModel } from "./hydration";
+import { readStorage, writeStorage } from "./storage";
+
+const storageKey = "sevenguis.crud.people";
 
 (async () => {
   await init("/wasm/sevenguis_bg.wasm");
 
-  const polyester = new Polyester(crudPage(), {
+  const polyester = new Polyester(crudPage(embeddedModel()), {
     loggerConfig: defaultDebugConfig(),
   });
 
+  polyester.onAppEffect((effect: any) => {
+    if (effect === "loadPeople") {
+      polyester.sendMessage("peopleLoaded", readStorage(storageKey));
+    } else if (effect.savePeople) {
+      writeStorage(storageKey, JSON.stringify(effect.savePeople));
+    } else if (effect.download) {
+      download(effect.download);
+    }
+  });
+
+  document.addEventListener("change", async (event) => {
+    const target = event.target as HTMLInputElement;
+    if (target.id !== "import-file" || !target.files?.length) {
+      return;
+    }
+
+    const file = target.files[0];
+    const content = await file.text();
+    target.value = "";
+
+    polyester.sendMessage("fileImported", { name: file.name, content });
+  });
+
   polyester.init();
+  bindUndoRedo(polyester);
 })();
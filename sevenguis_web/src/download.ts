@@ -0,0 +1,18 @@
+export interface Download {
+  filename: string;
+  mimeType: string;
+  content: string;
+}
+
+// Saves generated content as a file through a temporary link
+export function download({ filename, mimeType, content }: Download) {
+  const blob = new Blob([content], { type: mimeType });
+  const url = URL.createObjectURL(blob);
+
+  const link = document.createElement("a");
+  link.href = url;
+  link.download = filename;
+  link.click();
+
+  URL.revokeObjectURL(url);
+}
@@ -1,12 +1,10 @@
 import init, { flightPage } from "../wasm/sevenguis.js";
 import { Polyester } from "polyester";
-import { posixFromMilliseconds } from "polyester/src/time";
+import { embeddedModel } from "./hydration";
 
 (async () => {
   await init("/wasm/sevenguis_bg.wasm");
 
-  const currentTime = posixFromMilliseconds(Date.now());
-
-  const polyester = new Polyester(flightPage(currentTime));
+  const polyester = new Polyester(flightPage(embeddedModel()));
   polyester.init();
 })();
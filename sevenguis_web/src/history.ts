@@ -0,0 +1,26 @@
+import { Polyester } from "polyester";
+
+// Sends undo/redo messages on Ctrl+Z / Ctrl+Y (and Ctrl+Shift+Z),
+// leaving text fields to their native undo.
+export function bindUndoRedo(polyester: Polyester) {
+  document.addEventListener("keydown", (event) => {
+    const target = event.target as Element;
+    if (target.closest("input, textarea, select")) {
+      return;
+    }
+
+    if (!(event.ctrlKey || event.metaKey)) {
+      return;
+    }
+
+    const key = event.key.toLowerCase();
+
+    if (key === "z" && !event.shiftKey) {
+      event.preventDefault();
+      polyester.sendMessage("undo");
+    } else if (key === "y" || (key === "z" && event.shiftKey)) {
+      event.preventDefault();
+      polyester.sendMessage("redo");
+    }
+  });
+}
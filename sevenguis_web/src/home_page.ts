@@ -2,11 +2,12 @@ import init from "../wasm/sevenguis.js";
 import { homePage } from "../wasm/sevenguis";
 import { Polyester } from "polyester";
 import { defaultDebugConfig } from "polyester/src/logger";
+import { embeddedModel } from "./hydration";
 
 (async () => {
   await init("/wasm/sevenguis_bg.wasm");
 
-  const polyester = new Polyester(homePage(), {
+  const polyester = new Polyester(homePage(embeddedModel()), {
     loggerConfig: defaultDebugConfig(),
   });
 
@@ -0,0 +1,5 @@
+// The model the page was rendered from, if it was rendered with one
+export function embeddedModel(): string | undefined {
+  const element = document.getElementById("sevenguis-model");
+  return element?.textContent ?? undefined;
+}
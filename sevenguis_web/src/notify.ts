@@ -0,0 +1,43 @@
+export interface Notify {
+  title: string;
+  body: string;
+}
+
+export function requestNotificationPermission() {
+  if (!("Notification" in window) || Notification.permission !== "default") {
+    return;
+  }
+
+  Notification.requestPermission().catch((err) => {
+    console.warn("Failed to request notification permission", err);
+  });
+}
+
+// Plays a sound even when notifications aren't allowed
+export function notify({ title, body }: Notify) {
+  if ("Notification" in window && Notification.permission === "granted") {
+    new Notification(title, { body });
+  }
+
+  playBeep();
+}
+
+function playBeep() {
+  try {
+    const context = new AudioContext();
+    const oscillator = context.createOscillator();
+    const gain = context.createGain();
+
+    oscillator.frequency.value = 880;
+    gain.gain.setValueAtTime(0.2, context.currentTime);
+    gain.gain.exponentialRampToValueAtTime(0.001, context.currentTime + 0.6);
+
+    oscillator.connect(gain);
+    gain.connect(context.destination);
+    oscillator.start();
+    oscillator.stop(context.currentTime + 0.6);
+    oscillator.onended = () => context.close();
+  } catch (err) {
+    console.warn("Failed to play sound", err);
+  }
+}
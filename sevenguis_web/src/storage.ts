@@ -0,0 +1,17 @@
+// Storage can be unavailable, e.g. in private browsing, which shouldn't break the page
+export function readStorage(key: string): string | null {
+  try {
+    return localStorage.getItem(key);
+  } catch (err) {
+    console.warn("Failed to read from localStorage", err);
+    return null;
+  }
+}
+
+export function writeStorage(key: string, value: string) {
+  try {
+    localStorage.setItem(key, value);
+  } catch (err) {
+    console.warn("Failed to write to localStorage", err);
+  }
+}
@@ -1,11 +1,12 @@
 import init, { temperaturePage } from "../wasm/sevenguis.js";
 import { Polyester } from "polyester";
 import { defaultDebugConfig } from "polyester/src/logger";
+import { embeddedModel } from "./hydration";
 
 (async () => {
   await init("/wasm/sevenguis_bg.wasm");
 
-  const polyester = new Polyester(temperaturePage(), {
+  const polyester = new Polyester(temperaturePage(embeddedModel()), {
     loggerConfig: defaultDebugConfig(),
   });
 
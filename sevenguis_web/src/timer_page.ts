@@ -1,12 +1,38 @@
 import init, { timerPage } from "../wasm/sevenguis.js";
 import { Polyester } from "polyester";
-import { posixFromMilliseconds } from "polyester/src/time";
+import { download } from "./download";
+import { embeddedModel } from "./hydration";
+import { notify, requestNotificationPermission } from "./notify";
+import { readStorage, writeStorage } from "./storage";
+
+const storageKey = "sevenguis.timer.timers";
 
 (async () => {
   await init("/wasm/sevenguis_bg.wasm");
 
-  const currentTime = posixFromMilliseconds(Date.now());
+  const polyester = new Polyester(timerPage(embeddedModel()));
+
+  polyester.onAppEffect((effect: any) => {
+    if (effect === "loadTimers") {
+      polyester.sendMessage("timersLoaded", readStorage(storageKey));
+    } else if (effect.saveTimers) {
+      writeStorage(storageKey, JSON.stringify(effect.saveTimers));
+    } else if (effect === "requestNotificationPermission") {
+      requestNotificationPermission();
+    } else if (effect.notify) {
+      notify(effect.notify);
+    } else if (effect.download) {
+      download(effect.download);
+    }
+  });
 
-  const polyester = new Polyester(timerPage(currentTime));
   polyester.init();
+
+  // Lets the page stop ticking while nobody can see it
+  const sendVisibility = () => {
+    polyester.sendMessage("visibilityChanged", !document.hidden);
+  };
+
+  document.addEventListener("visibilitychange", sendVisibility);
+  sendVisibility();
 })();
@@ -1,6 +1,6 @@
 /** @type {import('tailwindcss').Config} */
 module.exports = {
-  content: ["../sevenguis_core/**/*_page.rs"],
+  content: ["../sevenguis_core/src/**/*.rs"],
   theme: {
     extend: {},
   },